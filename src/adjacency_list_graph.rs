@@ -0,0 +1,224 @@
+use graph_traits::{
+	GraphBase, GraphEdgeAddable, GraphEdgeEndpoints, GraphEdgeFrom, GraphEdgeIndexable,
+	GraphEdgeMutIndexable, GraphEdgeRemovable, GraphEdgeTo, GraphEdgesFrom, GraphNodeAddable,
+	GraphNodeIndexable, GraphNodeMutIndexable, GraphNodeRemovable,
+};
+
+use crate::traits::{EdgeIndex, GraphNodeIDs, NodeIndex};
+
+use std::iter::Iterator;
+
+/// Opaque struct which represents a node in the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeID(usize);
+
+/// Opaque struct which represents an edge in the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeID((usize, usize));
+
+impl NodeIndex for NodeID {
+	fn node_index(&self) -> usize { self.0 }
+}
+
+impl EdgeIndex for EdgeID {
+	fn edge_index(&self) -> (usize, usize) { self.0 }
+}
+
+/// A directed graph implementation backed by adjacency lists.
+///
+/// - Nodes are represented with `Vec<Option<N>>`
+///
+/// - Edges are represented with `Vec<Vec<(usize, E)>>`, holding a list of outgoing edges per node
+///
+/// - All IDs are opaque data structures
+///
+/// Memory usage grows with the number of nodes plus the number of edges, so this is better suited
+/// to large sparse graphs than `SimpleGraph`
+///
+/// Please see the trait implementations for more details
+#[derive(Clone, Debug)]
+pub struct AdjacencyListGraph<N, E> {
+	nodes: Vec<Option<N>>,
+	edges: Vec<Vec<(usize, E)>>,
+}
+
+impl<N, E> AdjacencyListGraph<N, E> {
+	pub fn new() -> Self {
+		Self {
+			nodes: Vec::new(),
+			edges: Vec::new(),
+		}
+	}
+
+	/// Whether a node is in the graph
+	fn has_node(&self, id: usize) -> bool { matches!(self.nodes.get(id), Some(Some(_))) }
+
+	/// Find the position of the edge from `a` to `b` within the edge list of `a`
+	fn edge_position(&self, a: usize, b: usize) -> Option<usize> {
+		self.edges[a].iter().position(|(to, _)| *to == b)
+	}
+}
+
+impl<N, E> Default for AdjacencyListGraph<N, E> {
+	fn default() -> Self { Self::new() }
+}
+
+impl<N, E> GraphBase for AdjacencyListGraph<N, E> {
+	type NodeID = NodeID;
+	type EdgeID = EdgeID;
+}
+
+impl<N, E> GraphNodeAddable<N> for AdjacencyListGraph<N, E> {
+	/// Identical data added twice will return different node IDs
+	fn add_node(&mut self, data: N) -> Self::NodeID {
+		self.nodes.push(Some(data));
+		self.edges.push(Vec::new());
+		NodeID(self.nodes.len() - 1)
+	}
+}
+
+impl<N, E> GraphEdgeAddable<E> for AdjacencyListGraph<N, E> {
+	/// Data added from node `A` to `B` will overwrite the data that was previously between those edges
+	///
+	/// Panics if either node is not in the graph
+	fn add_edge(
+		&mut self,
+		NodeID(a): Self::NodeID,
+		NodeID(b): Self::NodeID,
+		data: E,
+	) -> Self::EdgeID {
+		assert!(
+			self.has_node(a) && self.has_node(b),
+			"node is not in the graph"
+		);
+		match self.edge_position(a, b) {
+			Some(position) => self.edges[a][position].1 = data,
+			None => self.edges[a].push((b, data)),
+		}
+		EdgeID((a, b))
+	}
+}
+
+impl<N, E> GraphNodeRemovable<N> for AdjacencyListGraph<N, E> {
+	/// Panics if the node is not in the graph
+	///
+	/// Panics if any edges lead into or out of that node
+	fn remove_node(&mut self, NodeID(id): Self::NodeID) -> N {
+		// Panic if any edges point to or from the removed node
+		assert!(self.edges[id].is_empty());
+		for from_node in &self.edges {
+			assert!(from_node.iter().all(|(to, _)| *to != id));
+		}
+
+		self.nodes[id].take().unwrap()
+	}
+}
+
+impl<N, E> GraphEdgeRemovable<E> for AdjacencyListGraph<N, E> {
+	/// Panics if the edge is not in the graph
+	fn remove_edge(&mut self, EdgeID((id_a, id_b)): Self::EdgeID) -> E {
+		let position = self.edge_position(id_a, id_b).unwrap();
+		self.edges[id_a].swap_remove(position).1
+	}
+}
+
+impl<N, E> GraphNodeIndexable<N> for AdjacencyListGraph<N, E> {
+	/// Get the data associated with a node
+	///
+	/// Panics if the node is not in the graph
+	fn node(&self, NodeID(id): Self::NodeID) -> &N { self.nodes[id].as_ref().unwrap() }
+}
+
+impl<N, E> GraphNodeMutIndexable<N> for AdjacencyListGraph<N, E> {
+	/// Get the data associated with a node
+	///
+	/// Panics if the node is not in the graph
+	fn node_mut(&mut self, NodeID(id): Self::NodeID) -> &mut N { self.nodes[id].as_mut().unwrap() }
+}
+
+impl<N, E> GraphEdgeIndexable<E> for AdjacencyListGraph<N, E> {
+	/// Get the data associated with an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge(&self, EdgeID((id_a, id_b)): Self::EdgeID) -> &E {
+		let position = self.edge_position(id_a, id_b).unwrap();
+		&self.edges[id_a][position].1
+	}
+}
+
+impl<N, E> GraphEdgeMutIndexable<E> for AdjacencyListGraph<N, E> {
+	/// Get the data associated with an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge_mut(&mut self, EdgeID((id_a, id_b)): Self::EdgeID) -> &mut E {
+		let position = self.edge_position(id_a, id_b).unwrap();
+		&mut self.edges[id_a][position].1
+	}
+}
+
+impl<N, E> GraphEdgeTo for AdjacencyListGraph<N, E> {
+	/// Find the destination of an edge
+	fn edge_to(&self, EdgeID((_, id_b)): Self::EdgeID) -> Self::NodeID { NodeID(id_b) }
+}
+
+impl<N, E> GraphEdgeFrom for AdjacencyListGraph<N, E> {
+	/// Find the source of an edge
+	fn edge_from(&self, EdgeID((id_a, _)): Self::EdgeID) -> Self::NodeID { NodeID(id_a) }
+}
+
+impl<N, E> GraphEdgeEndpoints for AdjacencyListGraph<N, E> {}
+
+impl<N, E> GraphEdgesFrom for AdjacencyListGraph<N, E> {
+	type EdgesFromOutput = Vec<EdgeID>;
+
+	/// Find all edges from a node
+	///
+	/// Return result in a `Vec`
+	fn edges_from(&self, NodeID(id): Self::NodeID) -> Self::EdgesFromOutput {
+		self.edges[id]
+			.iter()
+			.map(|(to, _)| EdgeID((id, *to)))
+			.collect()
+	}
+}
+
+impl<N, E> GraphNodeIDs for AdjacencyListGraph<N, E> {
+	type NodeIDsOutput = Vec<NodeID>;
+
+	/// Find all nodes in the graph
+	///
+	/// Return result in a `Vec`
+	fn node_ids(&self) -> Self::NodeIDsOutput {
+		self.nodes
+			.iter()
+			.enumerate()
+			.filter_map(|(i, node)| node.as_ref().map(|_| NodeID(i)))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn overwrites_edges() {
+		let mut graph = AdjacencyListGraph::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let edge = graph.add_edge(a, b, 1);
+		assert_eq!(graph.add_edge(a, b, 2), edge);
+		assert_eq!(graph.edges_from(a), vec![edge]);
+		assert_eq!(*graph.edge(edge), 2);
+	}
+
+	#[test]
+	#[should_panic(expected = "node is not in the graph")]
+	fn rejects_edges_to_removed_nodes() {
+		let mut graph = AdjacencyListGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		graph.remove_node(b);
+		graph.add_edge(a, b, ());
+	}
+}
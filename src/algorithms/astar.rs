@@ -0,0 +1,165 @@
+use graph_traits::{GraphEdgeFrom, GraphEdgeIndexable, GraphEdgesFrom};
+
+use crate::algorithms::{Measure, MinScored, ShortestPaths};
+
+use std::{
+	collections::{BinaryHeap, HashMap},
+	hash::Hash,
+};
+
+/// Find the cheapest path from `start` to any node satisfying `is_goal`
+///
+/// `cost` gives the cost of traversing an edge from its data, and must never be negative
+///
+/// `heuristic` estimates the remaining cost from a node to the closest goal, and must never
+/// overestimate it for the returned path to be the cheapest
+///
+/// Returns the total cost along with the edges of the path, or `None` if no goal is reachable
+pub fn astar<G, E, W, IsGoal, F, H>(
+	graph: &G,
+	start: G::NodeID,
+	is_goal: IsGoal,
+	cost: F,
+	heuristic: H,
+) -> Option<(W, Vec<G::EdgeID>)>
+where
+	G: GraphEdgesFrom + GraphEdgeFrom + GraphEdgeIndexable<E>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	W: Measure,
+	IsGoal: Fn(G::NodeID) -> bool,
+	F: Fn(&E) -> W,
+	H: Fn(G::NodeID) -> W,
+{
+	let mut distances = HashMap::new();
+	let mut predecessors = HashMap::new();
+	let mut queue = BinaryHeap::new();
+
+	distances.insert(start, W::default());
+	queue.push(MinScored(heuristic(start), (start, W::default())));
+
+	while let Some(MinScored(_, (node, distance))) = queue.pop() {
+		// Skip stale queue entries for nodes which were since reached more cheaply
+		if distances.get(&node).is_some_and(|best| *best < distance) {
+			continue;
+		}
+
+		if is_goal(node) {
+			let paths = ShortestPaths {
+				distances,
+				predecessors,
+			};
+			return paths.path_to(graph, node).map(|path| (distance, path));
+		}
+
+		for edge in graph.edges_from(node) {
+			let to = graph.edge_to(edge);
+			let next = distance + cost(graph.edge(edge));
+			let improved = distances.get(&to).is_none_or(|current| next < *current);
+			if improved {
+				distances.insert(to, next);
+				predecessors.insert(to, edge);
+				queue.push(MinScored(next + heuristic(to), (to, next)));
+			}
+		}
+	}
+
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::simple_graph::{NodeID, SimpleGraph};
+	use graph_traits::{GraphEdgeAddable, GraphEdgeTo, GraphNodeAddable, GraphNodeIndexable};
+
+	/// A grid of cells joined to their neighbours in both directions, leaving out walled cells
+	fn grid(
+		width: i32,
+		height: i32,
+		walls: &[(i32, i32)],
+	) -> (SimpleGraph<(i32, i32), u32>, Vec<NodeID>) {
+		let mut graph = SimpleGraph::new();
+		let mut nodes = Vec::new();
+		for y in 0..height {
+			for x in 0..width {
+				nodes.push(graph.add_node((x, y)));
+			}
+		}
+		for y in 0..height {
+			for x in 0..width {
+				for (dx, dy) in [(1, 0), (0, 1)] {
+					let (nx, ny) = (x + dx, y + dy);
+					if nx >= width
+						|| ny >= height || walls.contains(&(x, y))
+						|| walls.contains(&(nx, ny))
+					{
+						continue;
+					}
+					let (a, b) = (
+						nodes[(y * width + x) as usize],
+						nodes[(ny * width + nx) as usize],
+					);
+					graph.add_edge(a, b, 1);
+					graph.add_edge(b, a, 1);
+				}
+			}
+		}
+		(graph, nodes)
+	}
+
+	#[test]
+	fn finds_cheapest_path_around_walls() {
+		// . . . .
+		// # # # .
+		// . . . .
+		let (graph, nodes) = grid(4, 3, &[(0, 1), (1, 1), (2, 1)]);
+		let goal = (0, 2);
+		let manhattan = |node: NodeID| {
+			let (x, y) = *graph.node(node);
+			((x - goal.0).abs() + (y - goal.1).abs()) as u32
+		};
+
+		let (cost, path) = astar(
+			&graph,
+			nodes[0],
+			|node| *graph.node(node) == goal,
+			|cost| *cost,
+			manhattan,
+		)
+		.unwrap();
+		assert_eq!(cost, 8);
+		assert_eq!(path.len(), 8);
+		assert_eq!(graph.edge_from(path[0]), nodes[0]);
+		assert_eq!(*graph.node(graph.edge_to(path[7])), goal);
+	}
+
+	#[test]
+	fn stops_at_the_closest_goal() {
+		let (graph, nodes) = grid(5, 1, &[]);
+		let (cost, _) = astar(
+			&graph,
+			nodes[2],
+			|node| node == nodes[0] || node == nodes[4],
+			|cost| *cost,
+			|_| 0,
+		)
+		.unwrap();
+		assert_eq!(cost, 2);
+	}
+
+	#[test]
+	fn unreachable_goal_gives_none() {
+		let (graph, nodes) = grid(3, 1, &[(1, 0)]);
+		assert_eq!(
+			astar(
+				&graph,
+				nodes[0],
+				|node| node == nodes[2],
+				|cost| *cost,
+				|_| 0
+			),
+			None
+		);
+	}
+}
@@ -0,0 +1,133 @@
+use graph_traits::{GraphEdgeFrom, GraphEdgeIndexable, GraphEdgesFrom};
+
+use crate::algorithms::{find_cycle, traversal::Bfs, Measure, ShortestPaths};
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A cycle of edges whose total cost is negative, so no cheapest path exists through it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycle<EdgeID>(pub Vec<EdgeID>);
+
+/// Either the cheapest paths from the start or a negative cycle which prevents them existing
+pub type BellmanFordResult<NodeID, EdgeID, W> =
+	Result<ShortestPaths<NodeID, EdgeID, W>, NegativeCycle<EdgeID>>;
+
+/// Find the cheapest path from `start` to every reachable node, allowing negative edge costs
+///
+/// `cost` gives the cost of traversing an edge from its data
+///
+/// Fails with one concrete cycle, in traversal order, if a negative cycle is reachable from
+/// `start`
+///
+/// Use `ShortestPaths::path_to` to reconstruct the path to a particular node
+pub fn bellman_ford<G, E, W, F>(
+	graph: &G,
+	start: G::NodeID,
+	cost: F,
+) -> BellmanFordResult<G::NodeID, G::EdgeID, W>
+where
+	G: GraphEdgesFrom + GraphEdgeFrom + GraphEdgeIndexable<E>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	W: Measure,
+	F: Fn(&E) -> W,
+{
+	// Only nodes reachable from the start can ever be given a distance
+	let nodes = Bfs::new(graph, start).collect::<Vec<_>>();
+	let edges = nodes
+		.iter()
+		.flat_map(|node| graph.edges_from(*node))
+		.map(|edge| {
+			let (from, to) = (graph.edge_from(edge), graph.edge_to(edge));
+			(edge, from, to, cost(graph.edge(edge)))
+		})
+		.collect::<Vec<_>>();
+
+	let mut distances = HashMap::new();
+	let mut predecessors = HashMap::new();
+	distances.insert(start, W::default());
+
+	// Relax every edge until no distances change, which takes fewer rounds than there are nodes
+	// unless there is a negative cycle
+	let mut last_changed = None;
+	for _ in 0..nodes.len() {
+		last_changed = None;
+		for (edge, from, to, weight) in &edges {
+			let next = match distances.get(from) {
+				Some(distance) => *distance + *weight,
+				None => continue,
+			};
+			if distances.get(to).is_none_or(|current| next < *current) {
+				distances.insert(*to, next);
+				predecessors.insert(*to, *edge);
+				last_changed = Some(*to);
+			}
+		}
+		if last_changed.is_none() {
+			break;
+		}
+	}
+
+	match last_changed {
+		// A distance changed in the final round, so its predecessors lead back into a cycle
+		Some(node) => Err(NegativeCycle(find_cycle(graph, &predecessors, node))),
+		None => Ok(ShortestPaths {
+			distances,
+			predecessors,
+		}),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{EdgeID, SimpleGraph},
+	};
+
+	#[test]
+	fn handles_negative_edges() {
+		let (graph, nodes): (SimpleGraph<(), i32>, _) =
+			test_graph(4, [(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)]);
+
+		let paths = bellman_ford(&graph, nodes[0], |cost| *cost).unwrap();
+		let distances = nodes
+			.iter()
+			.map(|node| paths.distance(*node))
+			.collect::<Vec<_>>();
+		assert_eq!(distances, vec![Some(0), Some(2), Some(5), Some(4)]);
+		assert_eq!(
+			paths.path_to(&graph, nodes[3]),
+			Some(vec![
+				EdgeID(nodes[0], nodes[2]),
+				EdgeID(nodes[2], nodes[1]),
+				EdgeID(nodes[1], nodes[3]),
+			])
+		);
+	}
+
+	#[test]
+	fn reports_reachable_negative_cycle() {
+		let (graph, nodes): (SimpleGraph<(), i32>, _) =
+			test_graph(4, [(0, 1, 1), (1, 2, 1), (2, 3, -4), (3, 1, 1)]);
+
+		let NegativeCycle(mut cycle) = bellman_ford(&graph, nodes[0], |cost| *cost).unwrap_err();
+		let start = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+		cycle.rotate_left(start);
+		assert_eq!(
+			cycle,
+			vec![
+				EdgeID(nodes[1], nodes[2]),
+				EdgeID(nodes[2], nodes[3]),
+				EdgeID(nodes[3], nodes[1]),
+			]
+		);
+	}
+
+	#[test]
+	fn ignores_unreachable_negative_cycle() {
+		let (graph, nodes): (SimpleGraph<(), i32>, _) = test_graph(2, [(1, 1, -1)]);
+		assert!(bellman_ford(&graph, nodes[0], |cost| *cost).is_ok());
+	}
+}
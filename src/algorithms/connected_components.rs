@@ -0,0 +1,50 @@
+use graph_traits::{GraphEdgeTo, GraphEdgesFrom};
+
+use crate::{traits::GraphNodeIDs, union_find::UnionFind};
+
+use std::hash::Hash;
+
+/// Find the connected components of a graph, ignoring the direction of its edges
+///
+/// Components are listed in the order their first node appears in `node_ids`, as are the nodes
+/// within each component
+pub fn weakly_connected_components<G>(graph: &G) -> Vec<Vec<G::NodeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let mut sets = UnionFind::new();
+	let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+	for node in &nodes {
+		sets.insert(*node);
+	}
+	for node in &nodes {
+		for edge in graph.edges_from(*node) {
+			sets.union(*node, graph.edge_to(edge));
+		}
+	}
+	sets.sets()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{algorithms::test_graph, simple_graph::SimpleGraph};
+
+	#[test]
+	fn ignores_edge_direction() {
+		let (graph, nodes): (SimpleGraph<(), ()>, _) =
+			test_graph(6, [(3, 0, ()), (4, 3, ()), (2, 5, ())]);
+
+		assert_eq!(
+			weakly_connected_components(&graph),
+			vec![
+				vec![nodes[0], nodes[3], nodes[4]],
+				vec![nodes[1]],
+				vec![nodes[2], nodes[5]],
+			]
+		);
+	}
+}
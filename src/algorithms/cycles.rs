@@ -0,0 +1,229 @@
+use graph_traits::{GraphEdgeFrom, GraphEdgesFrom};
+
+use crate::{algorithms::toposort::toposort_dfs, traits::GraphNodeIDs};
+
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
+/// Whether the directed graph contains any cycle, including self loops
+pub fn is_cyclic<G>(graph: &G) -> bool
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	toposort_dfs(graph).is_err()
+}
+
+/// Nodes in `candidates` which are reachable from `start` along the given adjacency lists
+fn reachable(adjacency: &[Vec<usize>], start: usize, candidates: &[bool]) -> Vec<bool> {
+	let mut reached = vec![false; adjacency.len()];
+	reached[start] = true;
+	let mut stack = vec![start];
+	while let Some(node) = stack.pop() {
+		for to in &adjacency[node] {
+			if candidates[*to] && !reached[*to] {
+				reached[*to] = true;
+				stack.push(*to);
+			}
+		}
+	}
+	reached
+}
+
+/// List every elementary cycle of the directed graph using Johnson's algorithm
+///
+/// Each cycle is given as its edges in traversal order, and visits no node more than once. Parallel
+/// edges give rise to distinct cycles
+///
+/// The number of cycles can grow exponentially with the size of the graph, so this is only
+/// suitable for graphs of moderate size
+pub fn elementary_cycles<G>(graph: &G) -> Vec<Vec<G::EdgeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+	let indices = nodes
+		.iter()
+		.enumerate()
+		.map(|(i, node)| (*node, i))
+		.collect::<HashMap<_, _>>();
+
+	let mut edges_from = vec![Vec::new(); nodes.len()];
+	let mut successors = vec![Vec::new(); nodes.len()];
+	let mut predecessors = vec![Vec::new(); nodes.len()];
+	for (from, node) in nodes.iter().enumerate() {
+		for edge in graph.edges_from(*node) {
+			let to = indices[&graph.edge_to(edge)];
+			edges_from[from].push((edge, to));
+			successors[from].push(to);
+			predecessors[to].push(from);
+		}
+	}
+
+	let mut cycles = Vec::new();
+	let mut blocked = vec![false; nodes.len()];
+	let mut blocked_by = vec![HashSet::new(); nodes.len()];
+
+	// Find the cycles whose smallest node is `start`, within the strongly connected component of
+	// `start` among the nodes no smaller than it
+	for start in 0..nodes.len() {
+		let candidates = (0..nodes.len()).map(|i| i >= start).collect::<Vec<_>>();
+		let forward = reachable(&successors, start, &candidates);
+		let backward = reachable(&predecessors, start, &candidates);
+		let component = forward
+			.iter()
+			.zip(&backward)
+			.map(|(forward, backward)| *forward && *backward)
+			.collect::<Vec<_>>();
+
+		for (i, in_component) in component.iter().enumerate() {
+			if *in_component {
+				blocked[i] = false;
+				blocked_by[i].clear();
+			}
+		}
+
+		// Each frame holds a node on the current path, the position of its next edge and whether
+		// any cycle has been found through it
+		let mut path = Vec::new();
+		let mut frames = vec![(start, 0, false)];
+		blocked[start] = true;
+		while let Some((node, next, found)) = frames.last_mut() {
+			let node = *node;
+			if let Some((edge, to)) = edges_from[node].get(*next).copied() {
+				*next += 1;
+				if !component[to] {
+					continue;
+				}
+				if to == start {
+					let mut cycle = path.clone();
+					cycle.push(edge);
+					cycles.push(cycle);
+					*found = true;
+				}
+				else if !blocked[to] {
+					path.push(edge);
+					blocked[to] = true;
+					frames.push((to, 0, false));
+				}
+				continue;
+			}
+
+			let found = *found;
+			frames.pop();
+			if found {
+				// Unblock the node along with everything waiting on it
+				let mut unblock = vec![node];
+				while let Some(node) = unblock.pop() {
+					if blocked[node] {
+						blocked[node] = false;
+						unblock.extend(blocked_by[node].drain());
+					}
+				}
+			}
+			else {
+				for (_, to) in &edges_from[node] {
+					if component[*to] {
+						blocked_by[*to].insert(node);
+					}
+				}
+			}
+			if let Some((_, _, parent_found)) = frames.last_mut() {
+				*parent_found |= found;
+				path.pop();
+			}
+		}
+	}
+
+	cycles
+}
+
+#[cfg(test)]
+mod tests {
+	use graph_traits::GraphEdgeTo;
+
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		multi_graph::MultiGraph,
+		simple_graph::{EdgeID, SimpleGraph},
+	};
+
+	/// Rotate every cycle to start at its smallest edge and sort them, so they can be compared
+	fn canonical<EdgeID: Ord + Clone>(cycles: Vec<Vec<EdgeID>>) -> Vec<Vec<EdgeID>> {
+		let mut cycles = cycles
+			.into_iter()
+			.map(|mut cycle| {
+				let start = (0..cycle.len()).min_by_key(|i| &cycle[*i]).unwrap();
+				cycle.rotate_left(start);
+				cycle
+			})
+			.collect::<Vec<_>>();
+		cycles.sort();
+		cycles
+	}
+
+	#[test]
+	fn lists_every_cycle_of_a_complete_graph() {
+		let (graph, nodes): (SimpleGraph<(), ()>, _) = test_graph(
+			3,
+			[
+				(0, 1, ()),
+				(1, 0, ()),
+				(0, 2, ()),
+				(2, 0, ()),
+				(1, 2, ()),
+				(2, 1, ()),
+				(0, 0, ()),
+			],
+		);
+		let edge = |from: usize, to: usize| EdgeID(nodes[from], nodes[to]);
+		let (ab, ba, ac, ca, bc, cb) = (
+			edge(0, 1),
+			edge(1, 0),
+			edge(0, 2),
+			edge(2, 0),
+			edge(1, 2),
+			edge(2, 1),
+		);
+		let aa = edge(0, 0);
+
+		let expected = vec![
+			vec![aa],
+			vec![ab, ba],
+			vec![ac, ca],
+			vec![bc, cb],
+			vec![ab, bc, ca],
+			vec![ac, cb, ba],
+		];
+		assert_eq!(canonical(elementary_cycles(&graph)), canonical(expected));
+		assert!(is_cyclic(&graph));
+	}
+
+	#[test]
+	fn parallel_edges_give_distinct_cycles() {
+		let (graph, _): (MultiGraph<(), ()>, _) =
+			test_graph(2, [(0, 1, ()), (0, 1, ()), (1, 0, ())]);
+
+		let cycles = elementary_cycles(&graph);
+		assert_eq!(cycles.len(), 2);
+		for cycle in cycles {
+			assert_eq!(graph.edge_to(cycle[0]), graph.edge_from(cycle[1]));
+		}
+	}
+
+	#[test]
+	fn acyclic_graph_has_no_cycles() {
+		let edges = (0..4).flat_map(|from| (from + 1..4).map(move |to| (from, to, ())));
+		let (graph, _): (SimpleGraph<(), ()>, _) = test_graph(4, edges);
+		assert!(!is_cyclic(&graph));
+		assert!(elementary_cycles(&graph).is_empty());
+	}
+}
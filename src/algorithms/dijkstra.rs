@@ -0,0 +1,106 @@
+use graph_traits::{GraphEdgeIndexable, GraphEdgeTo, GraphEdgesFrom};
+
+use crate::algorithms::{Measure, MinScored, ShortestPaths};
+
+use std::{
+	collections::{BinaryHeap, HashMap, HashSet},
+	hash::Hash,
+};
+
+/// Find the cheapest path from `start` to every reachable node
+///
+/// `cost` gives the cost of traversing an edge from its data, and must never be negative
+///
+/// Use `ShortestPaths::path_to` to reconstruct the path to a particular node
+pub fn dijkstra<G, E, W, F>(
+	graph: &G,
+	start: G::NodeID,
+	cost: F,
+) -> ShortestPaths<G::NodeID, G::EdgeID, W>
+where
+	G: GraphEdgesFrom + GraphEdgeTo + GraphEdgeIndexable<E>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	W: Measure,
+	F: Fn(&E) -> W,
+{
+	let mut distances = HashMap::new();
+	let mut predecessors = HashMap::new();
+	let mut finished = HashSet::new();
+	let mut queue = BinaryHeap::new();
+
+	distances.insert(start, W::default());
+	queue.push(MinScored(W::default(), start));
+
+	while let Some(MinScored(distance, node)) = queue.pop() {
+		// Skip stale queue entries for nodes which were already reached more cheaply
+		if !finished.insert(node) {
+			continue;
+		}
+
+		for edge in graph.edges_from(node) {
+			let to = graph.edge_to(edge);
+			if finished.contains(&to) {
+				continue;
+			}
+
+			let next = distance + cost(graph.edge(edge));
+			let improved = distances.get(&to).is_none_or(|current| next < *current);
+			if improved {
+				distances.insert(to, next);
+				predecessors.insert(to, edge);
+				queue.push(MinScored(next, to));
+			}
+		}
+	}
+
+	ShortestPaths {
+		distances,
+		predecessors,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use graph_traits::GraphNodeAddable;
+
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{EdgeID, SimpleGraph},
+	};
+
+	#[test]
+	fn finds_cheapest_paths() {
+		let (mut graph, nodes): (SimpleGraph<(), u32>, _) = test_graph(
+			5,
+			[
+				(0, 1, 7),
+				(0, 2, 9),
+				(0, 4, 14),
+				(1, 2, 10),
+				(1, 3, 15),
+				(2, 3, 11),
+				(2, 4, 2),
+			],
+		);
+		let unreachable = graph.add_node(());
+
+		let paths = dijkstra(&graph, nodes[0], |cost| *cost);
+		let distances = nodes
+			.iter()
+			.map(|node| paths.distance(*node))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			distances,
+			vec![Some(0), Some(7), Some(9), Some(20), Some(11)]
+		);
+		assert_eq!(paths.distance(unreachable), None);
+		assert_eq!(
+			paths.path_to(&graph, nodes[4]),
+			Some(vec![EdgeID(nodes[0], nodes[2]), EdgeID(nodes[2], nodes[4])])
+		);
+		assert_eq!(paths.path_to(&graph, nodes[0]), Some(vec![]));
+		assert_eq!(paths.path_to(&graph, unreachable), None);
+	}
+}
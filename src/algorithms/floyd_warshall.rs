@@ -0,0 +1,172 @@
+use crate::{
+	algorithms::{
+		bellman_ford::{bellman_ford, NegativeCycle},
+		Measure,
+	},
+	simple_graph::{EdgeID, NodeID, SimpleGraph},
+};
+
+/// The cheapest paths between every pair of nodes in a `SimpleGraph`
+///
+/// Holds a distance matrix and a next-hop matrix indexed by node slot, so lookups are constant
+/// time and paths take time proportional to their length
+#[derive(Clone, Debug)]
+pub struct AllPairsShortestPaths<W> {
+	nodes: Vec<Option<NodeID>>,
+	distances: Vec<Vec<Option<W>>>,
+	next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W> AllPairsShortestPaths<W>
+where
+	W: Copy,
+{
+	/// Find the slot of a node, if it was in the graph when the paths were computed
+	fn slot(&self, id: NodeID) -> Option<usize> {
+		match self.nodes.get(id.index) {
+			Some(Some(node)) if *node == id => Some(id.index),
+			_ => None,
+		}
+	}
+
+	/// The cost of the cheapest path from `from` to `to`, if one exists
+	pub fn distance(&self, from: NodeID, to: NodeID) -> Option<W> {
+		self.distances[self.slot(from)?][self.slot(to)?]
+	}
+
+	/// The node after `from` on the cheapest path from `from` to `to`, if one exists
+	///
+	/// This is `to` itself when `from` and `to` are the same node
+	pub fn next_hop(&self, from: NodeID, to: NodeID) -> Option<NodeID> {
+		let next = self.next[self.slot(from)?][self.slot(to)?]?;
+		self.nodes[next]
+	}
+
+	/// Reconstruct the cheapest path from `from` to `to` as a list of edges
+	///
+	/// Returns `None` if there is no such path
+	pub fn path(&self, from: NodeID, to: NodeID) -> Option<Vec<EdgeID>> {
+		let (mut current, target) = (self.slot(from)?, self.slot(to)?);
+		let mut path = Vec::new();
+		while current != target {
+			let next = self.next[current][target]?;
+			path.push(EdgeID(self.nodes[current]?, self.nodes[next]?));
+			current = next;
+		}
+		Some(path)
+	}
+}
+
+/// Find the cheapest path between every pair of nodes, allowing negative edge costs
+///
+/// Works directly on the edge matrix of the graph, taking time cubic and memory quadratic in the
+/// number of node slots, so it is best suited to small dense graphs
+///
+/// `cost` gives the cost of traversing an edge from its data
+///
+/// Fails with one concrete cycle, in traversal order, if the graph contains a negative cycle
+pub fn floyd_warshall<N, E, W, F>(
+	graph: &SimpleGraph<N, E>,
+	cost: F,
+) -> Result<AllPairsShortestPaths<W>, NegativeCycle<EdgeID>>
+where
+	W: Measure,
+	F: Fn(&E) -> W,
+{
+	let size = graph.node_ids().map(|id| id.index + 1).max().unwrap_or(0);
+	let mut nodes = vec![None; size];
+	for id in graph.node_ids() {
+		nodes[id.index] = Some(id);
+	}
+
+	let mut distances = vec![vec![None; size]; size];
+	let mut next = vec![vec![None; size]; size];
+	for (i, node) in nodes.iter().enumerate() {
+		if node.is_some() {
+			distances[i][i] = Some(W::default());
+			next[i][i] = Some(i);
+		}
+	}
+	for (i, row) in graph.edge_matrix().iter().enumerate() {
+		for (j, slot) in row.iter().enumerate() {
+			if let Some(data) = slot {
+				let weight = cost(data);
+				if distances[i][j].is_none_or(|current| weight < current) {
+					distances[i][j] = Some(weight);
+					next[i][j] = Some(j);
+				}
+			}
+		}
+	}
+
+	let zero = W::default();
+	for k in 0..size {
+		for i in 0..size {
+			let to_k = match distances[i][k] {
+				Some(distance) => distance,
+				None => continue,
+			};
+			for j in 0..size {
+				let through_k = match distances[k][j] {
+					Some(distance) => to_k + distance,
+					None => continue,
+				};
+				if distances[i][j].is_none_or(|current| through_k < current) {
+					// A node which can reach itself at a negative cost lies on a negative cycle,
+					// which Bellman-Ford can then report concretely. Stopping here keeps distances
+					// around the cycle from shrinking without bound
+					if i == j && through_k < zero {
+						return match bellman_ford(graph, nodes[i].unwrap(), cost) {
+							Err(cycle) => Err(cycle),
+							Ok(_) => unreachable!("negative cycle was not found by Bellman-Ford"),
+						};
+					}
+					distances[i][j] = Some(through_k);
+					next[i][j] = next[i][k];
+				}
+			}
+		}
+	}
+
+	Ok(AllPairsShortestPaths {
+		nodes,
+		distances,
+		next,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::algorithms::test_graph;
+
+	#[test]
+	fn finds_cheapest_paths() {
+		let (graph, nodes): (SimpleGraph<(), i32>, _) =
+			test_graph(4, [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, -1)]);
+		let (a, b, c, d) = (nodes[0], nodes[1], nodes[2], nodes[3]);
+
+		let paths = floyd_warshall(&graph, |weight| *weight).unwrap();
+		assert_eq!(paths.distance(a, b), Some(3));
+		assert_eq!(paths.distance(a, d), Some(2));
+		assert_eq!(paths.distance(d, a), None);
+		assert_eq!(paths.next_hop(a, d), Some(c));
+		assert_eq!(
+			paths.path(a, d),
+			Some(vec![EdgeID(a, c), EdgeID(c, b), EdgeID(b, d)])
+		);
+	}
+
+	#[test]
+	fn dense_negative_graph_reports_cycle_without_overflow() {
+		let edges = (0..40).flat_map(|from| (0..40).map(move |to| (from, to, -1)));
+		let (graph, _): (SimpleGraph<(), i32>, _) = test_graph(40, edges);
+
+		let NegativeCycle(cycle) = floyd_warshall(&graph, |weight| *weight).unwrap_err();
+		assert!(!cycle.is_empty());
+		for pair in cycle.windows(2) {
+			assert_eq!(pair[0].1, pair[1].0);
+		}
+		assert_eq!(cycle.last().unwrap().1, cycle[0].0);
+	}
+}
@@ -0,0 +1,349 @@
+use graph_traits::{GraphEdgeIndexable, GraphEdgeTo, GraphEdgesFrom};
+
+use crate::{algorithms::Measure, traits::GraphNodeIDs};
+
+use std::{
+	collections::{HashMap, VecDeque},
+	hash::Hash,
+	ops::Sub,
+};
+
+/// A maximum flow between two nodes along with the corresponding minimum cut
+#[derive(Clone, Debug)]
+pub struct MaxFlow<NodeID, EdgeID, W>
+where
+	EdgeID: Eq + Hash,
+{
+	/// The total flow leaving the source
+	pub value: W,
+	/// The flow sent along every edge
+	pub flows: HashMap<EdgeID, W>,
+	/// The nodes on the source side of a minimum cut
+	pub source_side: Vec<NodeID>,
+	/// The nodes on the sink side of a minimum cut
+	pub sink_side: Vec<NodeID>,
+}
+
+/// A residual network built from the graph
+///
+/// Every edge `k` becomes a forward arc `2 * k` and a backward arc `2 * k + 1`, so the paired arc
+/// of `a` is always `a ^ 1`
+struct Network<NodeID, EdgeID, W> {
+	nodes: Vec<NodeID>,
+	edges: Vec<(EdgeID, W)>,
+	targets: Vec<usize>,
+	residuals: Vec<W>,
+	arcs_from: Vec<Vec<usize>>,
+	source: usize,
+	sink: usize,
+	value: W,
+}
+
+impl<NodeID, EdgeID, W> Network<NodeID, EdgeID, W>
+where
+	NodeID: Copy + Eq + Hash,
+	EdgeID: Copy + Eq + Hash,
+	W: Measure + Sub<Output = W>,
+{
+	fn new<G, E, F>(graph: &G, source: NodeID, sink: NodeID, capacity: F) -> Self
+	where
+		G: GraphNodeIDs<NodeID = NodeID, EdgeID = EdgeID>
+			+ GraphEdgesFrom
+			+ GraphEdgeTo
+			+ GraphEdgeIndexable<E>,
+		G::NodeIDsOutput: IntoIterator<Item = NodeID>,
+		G::EdgesFromOutput: IntoIterator<Item = EdgeID>,
+		F: Fn(&E) -> W,
+	{
+		assert!(source != sink, "source and sink must be different nodes");
+
+		let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+		let indices = nodes
+			.iter()
+			.enumerate()
+			.map(|(i, node)| (*node, i))
+			.collect::<HashMap<_, _>>();
+		let source = *indices.get(&source).expect("source is not in the graph");
+		let sink = *indices.get(&sink).expect("sink is not in the graph");
+
+		let mut network = Self {
+			edges: Vec::new(),
+			targets: Vec::new(),
+			residuals: Vec::new(),
+			arcs_from: vec![Vec::new(); nodes.len()],
+			source,
+			sink,
+			nodes,
+			value: W::default(),
+		};
+		for (from, node) in network.nodes.iter().enumerate() {
+			for edge in graph.edges_from(*node) {
+				let to = indices[&graph.edge_to(edge)];
+				let capacity = capacity(graph.edge(edge));
+				network.arcs_from[from].push(network.targets.len());
+				network.targets.push(to);
+				network.residuals.push(capacity);
+				network.arcs_from[to].push(network.targets.len());
+				network.targets.push(from);
+				network.residuals.push(W::default());
+				network.edges.push((edge, capacity));
+			}
+		}
+		network
+	}
+
+	/// Whether flow can still be pushed along an arc
+	fn has_residual(&self, arc: usize) -> bool { self.residuals[arc] > W::default() }
+
+	/// Breadth-first search over arcs with residual capacity, giving the distance to each node
+	/// reachable from the source
+	fn levels(&self) -> Vec<Option<usize>> {
+		let mut levels = vec![None; self.nodes.len()];
+		levels[self.source] = Some(0);
+		let mut queue = VecDeque::new();
+		queue.push_back(self.source);
+		while let Some(node) = queue.pop_front() {
+			for arc in &self.arcs_from[node] {
+				let to = self.targets[*arc];
+				if levels[to].is_none() && self.has_residual(*arc) {
+					levels[to] = Some(levels[node].unwrap() + 1);
+					queue.push_back(to);
+				}
+			}
+		}
+		levels
+	}
+
+	/// Push as much flow as possible along a path of arcs from the source to the sink
+	fn augment(&mut self, path: &[usize]) {
+		let mut bottleneck = self.residuals[path[0]];
+		for arc in &path[1..] {
+			if self.residuals[*arc] < bottleneck {
+				bottleneck = self.residuals[*arc];
+			}
+		}
+		for arc in path {
+			self.residuals[*arc] = self.residuals[*arc] - bottleneck;
+			self.residuals[arc ^ 1] = self.residuals[arc ^ 1] + bottleneck;
+		}
+		self.value = self.value + bottleneck;
+	}
+
+	fn into_max_flow(self) -> MaxFlow<NodeID, EdgeID, W> {
+		let levels = self.levels();
+		let (mut source_side, mut sink_side) = (Vec::new(), Vec::new());
+		for (node, level) in self.nodes.iter().zip(&levels) {
+			match level {
+				Some(_) => source_side.push(*node),
+				None => sink_side.push(*node),
+			}
+		}
+
+		let mut flows = HashMap::new();
+		for (k, (edge, capacity)) in self.edges.iter().enumerate() {
+			flows.insert(*edge, *capacity - self.residuals[2 * k]);
+		}
+
+		MaxFlow {
+			value: self.value,
+			flows,
+			source_side,
+			sink_side,
+		}
+	}
+}
+
+/// Find a maximum flow from `source` to `sink` using the Edmonds-Karp algorithm
+///
+/// `capacity` gives the capacity of an edge from its data, and must never be negative
+///
+/// Panics if `source` and `sink` are the same node, or if either is not in the graph
+pub fn edmonds_karp<G, E, W, F>(
+	graph: &G,
+	source: G::NodeID,
+	sink: G::NodeID,
+	capacity: F,
+) -> MaxFlow<G::NodeID, G::EdgeID, W>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	G::EdgeID: Eq + Hash,
+	W: Measure + Sub<Output = W>,
+	F: Fn(&E) -> W,
+{
+	let mut network = Network::new(graph, source, sink, capacity);
+
+	// Augment along the shortest path with residual capacity until the sink is unreachable
+	loop {
+		let mut entered_by = vec![None; network.nodes.len()];
+		let mut queue = VecDeque::new();
+		queue.push_back(network.source);
+		while let Some(node) = queue.pop_front() {
+			for arc in &network.arcs_from[node] {
+				let to = network.targets[*arc];
+				if to != network.source && entered_by[to].is_none() && network.has_residual(*arc) {
+					entered_by[to] = Some(*arc);
+					queue.push_back(to);
+				}
+			}
+		}
+		if entered_by[network.sink].is_none() {
+			break;
+		}
+
+		let mut path = Vec::new();
+		let mut node = network.sink;
+		while let Some(arc) = entered_by[node] {
+			path.push(arc);
+			node = network.targets[arc ^ 1];
+		}
+		network.augment(&path);
+	}
+
+	network.into_max_flow()
+}
+
+/// Find a maximum flow from `source` to `sink` using Dinic's algorithm
+///
+/// `capacity` gives the capacity of an edge from its data, and must never be negative
+///
+/// Panics if `source` and `sink` are the same node, or if either is not in the graph
+pub fn dinic<G, E, W, F>(
+	graph: &G,
+	source: G::NodeID,
+	sink: G::NodeID,
+	capacity: F,
+) -> MaxFlow<G::NodeID, G::EdgeID, W>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	G::EdgeID: Eq + Hash,
+	W: Measure + Sub<Output = W>,
+	F: Fn(&E) -> W,
+{
+	let mut network = Network::new(graph, source, sink, capacity);
+
+	loop {
+		let mut levels = network.levels();
+		if levels[network.sink].is_none() {
+			break;
+		}
+
+		// Find a blocking flow in the level graph, remembering how far through its arcs each node
+		// has got so that saturated arcs are never retried
+		let mut next_arc = vec![0; network.nodes.len()];
+		let mut path = Vec::new();
+		let mut node = network.source;
+		loop {
+			if node == network.sink {
+				network.augment(&path);
+				path.clear();
+				node = network.source;
+				continue;
+			}
+
+			let advance = network.arcs_from[node][next_arc[node]..]
+				.iter()
+				.position(|arc| {
+					let to = network.targets[*arc];
+					network.has_residual(*arc) && levels[to] == levels[node].map(|level| level + 1)
+				});
+			match advance {
+				Some(offset) => {
+					next_arc[node] += offset;
+					let arc = network.arcs_from[node][next_arc[node]];
+					path.push(arc);
+					node = network.targets[arc];
+				}
+				None => {
+					// Nothing more can pass through this node, so retreat and never return to it
+					next_arc[node] = network.arcs_from[node].len();
+					if node == network.source {
+						break;
+					}
+					levels[node] = None;
+					let arc = path.pop().unwrap();
+					node = network.targets[arc ^ 1];
+					next_arc[node] += 1;
+				}
+			}
+		}
+	}
+
+	network.into_max_flow()
+}
+
+#[cfg(test)]
+mod tests {
+	use graph_traits::{GraphNodeAddable, GraphNodeRemovable};
+
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{EdgeID, NodeID, SimpleGraph},
+	};
+
+	/// The flow network from Cormen et al., whose maximum flow is 23
+	fn network() -> (SimpleGraph<(), u32>, Vec<NodeID>) {
+		test_graph(
+			6,
+			[
+				(0, 1, 16),
+				(0, 2, 13),
+				(1, 3, 12),
+				(2, 1, 4),
+				(2, 4, 14),
+				(3, 2, 9),
+				(3, 5, 20),
+				(4, 3, 7),
+				(4, 5, 4),
+			],
+		)
+	}
+
+	fn check(graph: &SimpleGraph<(), u32>, nodes: &[NodeID], flow: &MaxFlow<NodeID, EdgeID, u32>) {
+		assert_eq!(flow.value, 23);
+		for (edge, amount) in &flow.flows {
+			assert!(*amount <= *graph.edge(*edge));
+		}
+		assert!(flow.source_side.contains(&nodes[0]));
+		assert!(flow.sink_side.contains(&nodes[5]));
+
+		// The edges crossing the cut are saturated and their capacities add up to the flow
+		let cut = graph
+			.edges()
+			.filter(|(_, from, to, _)| {
+				flow.source_side.contains(from) && flow.sink_side.contains(to)
+			})
+			.map(|(_, _, _, capacity)| *capacity)
+			.sum::<u32>();
+		assert_eq!(cut, 23);
+	}
+
+	#[test]
+	fn edmonds_karp_finds_known_maximum() {
+		let (graph, nodes) = network();
+		let flow = edmonds_karp(&graph, nodes[0], nodes[5], |capacity| *capacity);
+		check(&graph, &nodes, &flow);
+	}
+
+	#[test]
+	fn dinic_finds_known_maximum() {
+		let (graph, nodes) = network();
+		let flow = dinic(&graph, nodes[0], nodes[5], |capacity| *capacity);
+		check(&graph, &nodes, &flow);
+	}
+
+	#[test]
+	#[should_panic(expected = "sink is not in the graph")]
+	fn rejects_removed_sink() {
+		let (mut graph, nodes) = network();
+		let removed = graph.add_node(());
+		graph.remove_node(removed);
+		dinic(&graph, nodes[0], removed, |capacity| *capacity);
+	}
+}
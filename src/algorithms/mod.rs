@@ -0,0 +1,137 @@
+//! Graph algorithms
+//!
+//! Most are generic over the traits from `graph_traits`, so they work on every graph in this crate
+//! as well as any other implementor
+
+pub mod astar;
+pub mod bellman_ford;
+pub mod connected_components;
+pub mod cycles;
+pub mod dijkstra;
+pub mod floyd_warshall;
+pub mod max_flow;
+pub mod mst;
+pub mod scc;
+pub mod toposort;
+pub mod traversal;
+
+use graph_traits::GraphEdgeFrom;
+#[cfg(test)]
+use graph_traits::{GraphEdgeAddable, GraphNodeAddable};
+
+use std::{cmp::Ordering, collections::HashMap, hash::Hash, ops::Add};
+
+/// A path cost which can be summed and compared, such as an integer or a float
+///
+/// `Default::default()` is used as the cost of an empty path
+pub trait Measure: Copy + PartialOrd + Add<Output = Self> + Default {}
+
+impl<T> Measure for T where T: Copy + PartialOrd + Add<Output = T> + Default {}
+
+/// The result of a single-source shortest path search
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<NodeID, EdgeID, W>
+where
+	NodeID: Eq + Hash,
+{
+	/// The cost of the cheapest path to every reachable node
+	pub distances: HashMap<NodeID, W>,
+	/// The last edge on the cheapest path to every reachable node other than the start
+	pub predecessors: HashMap<NodeID, EdgeID>,
+}
+
+impl<NodeID, EdgeID, W> ShortestPaths<NodeID, EdgeID, W>
+where
+	NodeID: Copy + Eq + Hash,
+	EdgeID: Copy,
+	W: Copy,
+{
+	/// The cost of the cheapest path to a node, if it is reachable
+	pub fn distance(&self, id: NodeID) -> Option<W> { self.distances.get(&id).copied() }
+
+	/// Reconstruct the cheapest path to a node as a list of edges from the start
+	///
+	/// Returns `None` if the node is not reachable
+	pub fn path_to<G>(&self, graph: &G, target: NodeID) -> Option<Vec<EdgeID>>
+	where
+		G: GraphEdgeFrom<NodeID = NodeID, EdgeID = EdgeID>,
+	{
+		if !self.distances.contains_key(&target) {
+			return None;
+		}
+
+		let mut path = Vec::new();
+		let mut current = target;
+		while let Some(edge) = self.predecessors.get(&current) {
+			path.push(*edge);
+			current = graph.edge_from(*edge);
+		}
+		path.reverse();
+		Some(path)
+	}
+}
+
+/// Follow the predecessor edges back from `node` and return the first cycle reached
+pub(crate) fn find_cycle<G>(
+	graph: &G,
+	predecessors: &HashMap<G::NodeID, G::EdgeID>,
+	node: G::NodeID,
+) -> Vec<G::EdgeID>
+where
+	G: GraphEdgeFrom,
+	G::NodeID: Eq + Hash,
+{
+	let mut seen = HashMap::new();
+	let mut path = Vec::new();
+	let mut current = node;
+	while !seen.contains_key(&current) {
+		seen.insert(current, path.len());
+		let edge = predecessors[&current];
+		path.push(edge);
+		current = graph.edge_from(edge);
+	}
+
+	// The edges walked since `current` was first seen form the cycle, in reverse order
+	let mut cycle = path.split_off(seen[&current]);
+	cycle.reverse();
+	cycle
+}
+
+/// An entry in a priority queue which pops the lowest score first
+///
+/// Scores which cannot be compared, such as NaN, are treated as equal
+pub(crate) struct MinScored<W, T>(pub W, pub T);
+
+impl<W: PartialOrd, T> PartialEq for MinScored<W, T> {
+	fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl<W: PartialOrd, T> Eq for MinScored<W, T> {}
+
+impl<W: PartialOrd, T> PartialOrd for MinScored<W, T> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl<W: PartialOrd, T> Ord for MinScored<W, T> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+	}
+}
+
+/// Build a graph of `node_count` nodes joined by `edges`, each given as `(from, to, data)` with
+/// the positions of its endpoints in the returned list of node IDs
+#[cfg(test)]
+pub(crate) fn test_graph<G, E, I>(node_count: usize, edges: I) -> (G, Vec<G::NodeID>)
+where
+	G: Default + GraphNodeAddable<()> + GraphEdgeAddable<E>,
+	I: IntoIterator<Item = (usize, usize, E)>,
+{
+	let mut graph = G::default();
+	let nodes = (0..node_count)
+		.map(|_| graph.add_node(()))
+		.collect::<Vec<_>>();
+	for (from, to, data) in edges {
+		graph.add_edge(nodes[from], nodes[to], data);
+	}
+	(graph, nodes)
+}
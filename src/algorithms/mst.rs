@@ -0,0 +1,224 @@
+use graph_traits::{GraphEdgeFrom, GraphEdgeIndexable, GraphEdgesFrom};
+
+use crate::{
+	algorithms::{Measure, MinScored},
+	traits::GraphNodeIDs,
+	union_find::UnionFind,
+};
+
+use std::{
+	cmp::Ordering,
+	collections::{BinaryHeap, HashMap, HashSet},
+	hash::Hash,
+};
+
+/// A minimum spanning forest, holding a minimum spanning tree for each connected component
+#[derive(Clone, Debug)]
+pub struct SpanningForest<EdgeID, W> {
+	/// The edges making up the forest
+	pub edges: Vec<EdgeID>,
+	/// The sum of the weights of every edge in the forest
+	pub total_weight: W,
+}
+
+/// An edge along with its endpoints and weight
+struct WeightedEdge<EdgeID, NodeID, W> {
+	edge: EdgeID,
+	from: NodeID,
+	to: NodeID,
+	weight: W,
+}
+
+/// Collect every distinct edge in the graph along with its endpoints and weight
+fn weighted_edges<G, E, W, F>(graph: &G, weight: F) -> Vec<WeightedEdge<G::EdgeID, G::NodeID, W>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::EdgeID: Eq + Hash,
+	F: Fn(&E) -> W,
+{
+	// Undirected graphs report each edge from both of its endpoints
+	let mut seen = HashSet::new();
+	let mut edges = Vec::new();
+	for node in graph.node_ids() {
+		for edge in graph.edges_from(node) {
+			if seen.insert(edge) {
+				edges.push(WeightedEdge {
+					edge,
+					from: node,
+					to: graph.edge_to(edge),
+					weight: weight(graph.edge(edge)),
+				});
+			}
+		}
+	}
+	edges
+}
+
+/// Find a minimum spanning forest using Kruskal's algorithm
+///
+/// Edge directions are ignored, and `weight` gives the weight of an edge from its data
+pub fn kruskal<G, E, W, F>(graph: &G, weight: F) -> SpanningForest<G::EdgeID, W>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	G::EdgeID: Eq + Hash,
+	W: Measure,
+	F: Fn(&E) -> W,
+{
+	let mut edges = weighted_edges(graph, weight);
+	edges.sort_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap_or(Ordering::Equal));
+
+	// Take the lightest edges which join two separate trees
+	let mut trees = UnionFind::new();
+	let mut forest = SpanningForest {
+		edges: Vec::new(),
+		total_weight: W::default(),
+	};
+	for edge in edges {
+		if trees.union(edge.from, edge.to) {
+			forest.edges.push(edge.edge);
+			forest.total_weight = forest.total_weight + edge.weight;
+		}
+	}
+	forest
+}
+
+/// Find a minimum spanning forest using Prim's algorithm
+///
+/// Edge directions are ignored, and `weight` gives the weight of an edge from its data
+pub fn prim<G, E, W, F>(graph: &G, weight: F) -> SpanningForest<G::EdgeID, W>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	G::EdgeID: Eq + Hash,
+	W: Measure,
+	F: Fn(&E) -> W,
+{
+	let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+
+	// Every edge can be crossed from either end
+	let mut neighbours = HashMap::<_, Vec<_>>::new();
+	for edge in weighted_edges(graph, weight) {
+		let WeightedEdge {
+			edge,
+			from,
+			to,
+			weight,
+		} = edge;
+		neighbours.entry(from).or_default().push((edge, to, weight));
+		neighbours.entry(to).or_default().push((edge, from, weight));
+	}
+
+	let mut forest = SpanningForest {
+		edges: Vec::new(),
+		total_weight: W::default(),
+	};
+	let mut in_forest = HashSet::new();
+	let mut queue = BinaryHeap::new();
+
+	// Grow a tree from each node which is not yet covered by an earlier tree
+	for root in nodes {
+		if !in_forest.insert(root) {
+			continue;
+		}
+		for (edge, to, weight) in neighbours.get(&root).into_iter().flatten() {
+			queue.push(MinScored(*weight, (*edge, *to)));
+		}
+
+		while let Some(MinScored(weight, (edge, node))) = queue.pop() {
+			if !in_forest.insert(node) {
+				continue;
+			}
+			forest.edges.push(edge);
+			forest.total_weight = forest.total_weight + weight;
+			for (edge, to, weight) in neighbours.get(&node).into_iter().flatten() {
+				if !in_forest.contains(to) {
+					queue.push(MinScored(*weight, (*edge, *to)));
+				}
+			}
+		}
+	}
+	forest
+}
+
+#[cfg(test)]
+mod tests {
+	use graph_traits::GraphEdgeTo;
+
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{EdgeID, SimpleGraph},
+		undirected_graph::UndirectedGraph,
+	};
+
+	#[test]
+	fn spans_every_component() {
+		let (graph, nodes): (SimpleGraph<(), u32>, _) = test_graph(
+			6,
+			[
+				(0, 1, 1),
+				(2, 1, 2),
+				(0, 2, 3),
+				(2, 3, 4),
+				(3, 1, 5),
+				(5, 4, 7),
+			],
+		);
+
+		let expected = vec![
+			EdgeID(nodes[0], nodes[1]),
+			EdgeID(nodes[2], nodes[1]),
+			EdgeID(nodes[2], nodes[3]),
+			EdgeID(nodes[5], nodes[4]),
+		];
+		for mut forest in [
+			kruskal(&graph, |weight| *weight),
+			prim(&graph, |weight| *weight),
+		] {
+			forest.edges.sort();
+			assert_eq!(forest.edges, expected);
+			assert_eq!(forest.total_weight, 14);
+		}
+	}
+
+	#[test]
+	fn counts_undirected_edges_once() {
+		// The second edge between nodes 1 and 0 replaces the first
+		let (graph, nodes): (UndirectedGraph<(), u32>, _) = test_graph(
+			4,
+			[
+				(0, 1, 9),
+				(1, 0, 1),
+				(1, 2, 2),
+				(2, 0, 3),
+				(3, 2, 5),
+				(3, 3, 0),
+			],
+		);
+
+		let index = |node| nodes.iter().position(|other| *other == node).unwrap();
+		for forest in [
+			kruskal(&graph, |weight| *weight),
+			prim(&graph, |weight| *weight),
+		] {
+			let mut endpoints = forest
+				.edges
+				.iter()
+				.map(|edge| {
+					let (from, to) = (index(graph.edge_from(*edge)), index(graph.edge_to(*edge)));
+					(from.min(to), from.max(to))
+				})
+				.collect::<Vec<_>>();
+			endpoints.sort();
+			assert_eq!(endpoints, vec![(0, 1), (1, 2), (2, 3)]);
+			assert_eq!(forest.total_weight, 8);
+		}
+	}
+}
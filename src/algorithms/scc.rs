@@ -0,0 +1,315 @@
+use graph_traits::{
+	GraphEdgeAddable, GraphEdgeTo, GraphEdgesFrom, GraphNodeAddable, GraphNodeIndexable,
+};
+
+use crate::{
+	algorithms::traversal::{Dfs, DfsEvent},
+	simple_graph::SimpleGraph,
+	traits::GraphNodeIDs,
+};
+
+use std::{
+	cmp::min,
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
+/// Bookkeeping for Tarjan's algorithm
+struct Tarjan<NodeID> {
+	indices: HashMap<NodeID, usize>,
+	low_links: HashMap<NodeID, usize>,
+	stack: Vec<NodeID>,
+	on_stack: HashSet<NodeID>,
+}
+
+impl<NodeID> Tarjan<NodeID>
+where
+	NodeID: Copy + Eq + Hash,
+{
+	/// Number a node and push it onto the stack of nodes without a component
+	fn visit(&mut self, node: NodeID) {
+		let index = self.indices.len();
+		self.indices.insert(node, index);
+		self.low_links.insert(node, index);
+		self.stack.push(node);
+		self.on_stack.insert(node);
+	}
+
+	/// Lower the low link of a node if `low_link` is smaller
+	fn lower(&mut self, node: NodeID, low_link: usize) {
+		let current = self.low_links.get_mut(&node).unwrap();
+		*current = min(*current, low_link);
+	}
+}
+
+/// Find the strongly connected components of a directed graph using Tarjan's algorithm
+///
+/// Components are returned in reverse topological order, so no edge leads from a component to
+/// one earlier in the list
+pub fn tarjan_scc<G>(graph: &G) -> Vec<Vec<G::NodeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let mut state = Tarjan {
+		indices: HashMap::new(),
+		low_links: HashMap::new(),
+		stack: Vec::new(),
+		on_stack: HashSet::new(),
+	};
+	let mut components = Vec::new();
+
+	for root in graph.node_ids() {
+		if state.indices.contains_key(&root) {
+			continue;
+		}
+
+		// Emulate the recursion with an explicit stack of nodes and their unexplored edges
+		state.visit(root);
+		let mut calls = vec![(root, graph.edges_from(root).into_iter())];
+		while let Some((node, edges)) = calls.last_mut() {
+			let node = *node;
+			match edges.next() {
+				Some(edge) => {
+					let to = graph.edge_to(edge);
+					if !state.indices.contains_key(&to) {
+						state.visit(to);
+						calls.push((to, graph.edges_from(to).into_iter()));
+					}
+					else if state.on_stack.contains(&to) {
+						state.lower(node, state.indices[&to]);
+					}
+				}
+				None => {
+					calls.pop();
+
+					// The node is the root of a component, which is everything above it on the stack
+					if state.low_links[&node] == state.indices[&node] {
+						let mut component = Vec::new();
+						loop {
+							let member = state.stack.pop().unwrap();
+							state.on_stack.remove(&member);
+							component.push(member);
+							if member == node {
+								break;
+							}
+						}
+						components.push(component);
+					}
+
+					if let Some((parent, _)) = calls.last() {
+						state.lower(*parent, state.low_links[&node]);
+					}
+				}
+			}
+		}
+	}
+
+	components
+}
+
+/// Find the strongly connected components of a directed graph using Kosaraju's algorithm
+///
+/// Components are returned in reverse topological order, so no edge leads from a component to
+/// one earlier in the list
+pub fn kosaraju_scc<G>(graph: &G) -> Vec<Vec<G::NodeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+
+	// Record the order in which nodes finish along with the edges reversed
+	let mut finish_order = Vec::with_capacity(nodes.len());
+	for event in Dfs::with_roots(graph, nodes.iter().copied()) {
+		if let DfsEvent::Finish(node) = event {
+			finish_order.push(node);
+		}
+	}
+	let mut reversed = HashMap::<_, Vec<_>>::new();
+	for node in &nodes {
+		for edge in graph.edges_from(*node) {
+			reversed.entry(graph.edge_to(edge)).or_default().push(*node);
+		}
+	}
+
+	// Searching the reversed graph from the latest finished node first yields components in
+	// topological order
+	let mut assigned = HashSet::new();
+	let mut components = Vec::new();
+	for root in finish_order.into_iter().rev() {
+		if !assigned.insert(root) {
+			continue;
+		}
+		let mut component = Vec::new();
+		let mut stack = vec![root];
+		while let Some(node) = stack.pop() {
+			component.push(node);
+			for from in reversed.get(&node).into_iter().flatten() {
+				if assigned.insert(*from) {
+					stack.push(*from);
+				}
+			}
+		}
+		components.push(component);
+	}
+
+	components.reverse();
+	components
+}
+
+/// Collapse each strongly connected component into a single node, producing an acyclic graph
+///
+/// Each node holds the members of its component, and each edge holds every original edge leading
+/// from one component to the other
+///
+/// Components are added in the order returned by `tarjan_scc`, and the original edges are listed
+/// in the order of the members they leave from, so the result is the same on every run
+pub fn condensation<G>(graph: &G) -> SimpleGraph<Vec<G::NodeID>, Vec<G::EdgeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let mut condensed = SimpleGraph::new();
+	let mut component_of = HashMap::new();
+	let mut ids = Vec::new();
+	for component in tarjan_scc(graph) {
+		let id = condensed.add_node(component);
+		for member in condensed.node(id) {
+			component_of.insert(*member, id);
+		}
+		ids.push(id);
+	}
+
+	// Group the edges between each pair of components, in the order the components and their
+	// members were found so the result does not depend on hashing
+	let mut positions = HashMap::new();
+	let mut between = Vec::<(_, _, Vec<_>)>::new();
+	for from in ids {
+		for member in condensed.node(from) {
+			for edge in graph.edges_from(*member) {
+				let to = component_of[&graph.edge_to(edge)];
+				if to != from {
+					let position = *positions.entry((from, to)).or_insert_with(|| {
+						between.push((from, to, Vec::new()));
+						between.len() - 1
+					});
+					between[position].2.push(edge);
+				}
+			}
+		}
+	}
+	for (from, to, edges) in between {
+		condensed.add_edge(from, to, edges);
+	}
+
+	condensed
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{EdgeID, NodeID},
+	};
+
+	fn example() -> (SimpleGraph<(), ()>, Vec<NodeID>) {
+		test_graph(
+			6,
+			[
+				(0, 1, ()),
+				(1, 2, ()),
+				(2, 0, ()),
+				(2, 3, ()),
+				(3, 4, ()),
+				(4, 3, ()),
+				(4, 5, ()),
+			],
+		)
+	}
+
+	#[test]
+	fn finds_components_in_reverse_topological_order() {
+		let (graph, nodes) = example();
+		let expected = vec![
+			vec![nodes[5]],
+			vec![nodes[3], nodes[4]],
+			vec![nodes[0], nodes[1], nodes[2]],
+		];
+		for mut components in [tarjan_scc(&graph), kosaraju_scc(&graph)] {
+			for component in &mut components {
+				component.sort();
+			}
+			assert_eq!(components, expected);
+		}
+	}
+
+	#[test]
+	fn condenses_components() {
+		let (graph, nodes) = example();
+		let condensed = condensation(&graph);
+		assert_eq!(condensed.node_count(), 3);
+
+		let mut edges = condensed
+			.edges()
+			.map(|(_, from, to, edges)| {
+				let mut from = condensed.node(from).clone();
+				let mut to = condensed.node(to).clone();
+				from.sort();
+				to.sort();
+				(from, to, edges.clone())
+			})
+			.collect::<Vec<_>>();
+		edges.sort();
+		assert_eq!(
+			edges,
+			vec![
+				(
+					vec![nodes[0], nodes[1], nodes[2]],
+					vec![nodes[3], nodes[4]],
+					vec![EdgeID(nodes[2], nodes[3])],
+				),
+				(
+					vec![nodes[3], nodes[4]],
+					vec![nodes[5]],
+					vec![EdgeID(nodes[4], nodes[5])],
+				),
+			]
+		);
+	}
+
+	#[test]
+	fn lists_edges_between_components_in_member_order() {
+		let (graph, nodes): (SimpleGraph<(), ()>, _) =
+			test_graph(3, [(0, 1, ()), (1, 0, ()), (0, 2, ()), (1, 2, ())]);
+
+		for _ in 0..8 {
+			let condensed = condensation(&graph);
+			let edges = condensed
+				.edges()
+				.map(|(_, from, to, edges)| {
+					(
+						condensed.node(from).clone(),
+						condensed.node(to).clone(),
+						edges.clone(),
+					)
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(
+				edges,
+				vec![(
+					vec![nodes[1], nodes[0]],
+					vec![nodes[2]],
+					vec![EdgeID(nodes[1], nodes[2]), EdgeID(nodes[0], nodes[2])],
+				)]
+			);
+		}
+	}
+}
@@ -0,0 +1,178 @@
+use graph_traits::{GraphEdgeFrom, GraphEdgesFrom};
+
+use crate::{algorithms::find_cycle, traits::GraphNodeIDs};
+
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	hash::Hash,
+};
+
+/// A cycle of edges, in traversal order, which prevents a topological order from existing
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<EdgeID>(pub Vec<EdgeID>);
+
+/// Order the nodes so that every edge leads from an earlier node to a later one, using Kahn's
+/// algorithm
+///
+/// Nodes with no constraints between them keep the order given by `node_ids`
+///
+/// Fails with one concrete cycle if the graph is not acyclic
+pub fn toposort_kahn<G>(graph: &G) -> Result<Vec<G::NodeID>, Cycle<G::EdgeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+
+	let mut in_degrees = nodes
+		.iter()
+		.map(|node| (*node, 0usize))
+		.collect::<HashMap<_, _>>();
+	for node in &nodes {
+		for edge in graph.edges_from(*node) {
+			*in_degrees.get_mut(&graph.edge_to(edge)).unwrap() += 1;
+		}
+	}
+
+	let mut queue = nodes
+		.iter()
+		.copied()
+		.filter(|node| in_degrees[node] == 0)
+		.collect::<VecDeque<_>>();
+	let mut order = Vec::with_capacity(nodes.len());
+	while let Some(node) = queue.pop_front() {
+		order.push(node);
+		for edge in graph.edges_from(node) {
+			let to = graph.edge_to(edge);
+			let in_degree = in_degrees.get_mut(&to).unwrap();
+			*in_degree -= 1;
+			if *in_degree == 0 {
+				queue.push_back(to);
+			}
+		}
+	}
+
+	if order.len() == nodes.len() {
+		return Ok(order);
+	}
+
+	// Every node left over still has an edge leading in from another left over node, so walking
+	// backwards along those edges must eventually loop
+	let mut predecessors = HashMap::new();
+	for node in nodes.iter().filter(|node| in_degrees[node] > 0) {
+		for edge in graph.edges_from(*node) {
+			let to = graph.edge_to(edge);
+			if in_degrees[&to] > 0 {
+				predecessors.insert(to, edge);
+			}
+		}
+	}
+	let start = *predecessors.keys().next().unwrap();
+	Err(Cycle(find_cycle(graph, &predecessors, start)))
+}
+
+/// Order the nodes so that every edge leads from an earlier node to a later one, using a
+/// depth-first search
+///
+/// Fails with one concrete cycle if the graph is not acyclic
+pub fn toposort_dfs<G>(graph: &G) -> Result<Vec<G::NodeID>, Cycle<G::EdgeID>>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeFrom,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	let mut finished = HashSet::new();
+	let mut order = Vec::new();
+
+	for root in graph.node_ids() {
+		if finished.contains(&root) {
+			continue;
+		}
+
+		// Each stack entry holds a node, the edge it was reached by and its unexplored edges
+		let mut stack = vec![(root, None, graph.edges_from(root).into_iter())];
+		let mut on_stack = HashMap::new();
+		on_stack.insert(root, 0);
+
+		while let Some((node, _, edges)) = stack.last_mut() {
+			let node = *node;
+			match edges.next() {
+				Some(edge) => {
+					let to = graph.edge_to(edge);
+					if let Some(position) = on_stack.get(&to) {
+						// A back edge closes a cycle through the nodes above `to` on the stack
+						let mut cycle = stack[position + 1..]
+							.iter()
+							.filter_map(|(_, entered_by, _)| *entered_by)
+							.collect::<Vec<_>>();
+						cycle.push(edge);
+						return Err(Cycle(cycle));
+					}
+					if !finished.contains(&to) {
+						on_stack.insert(to, stack.len());
+						stack.push((to, Some(edge), graph.edges_from(to).into_iter()));
+					}
+				}
+				None => {
+					stack.pop();
+					on_stack.remove(&node);
+					finished.insert(node);
+					order.push(node);
+				}
+			}
+		}
+	}
+
+	// Nodes were finished after everything reachable from them, so the reverse is a valid order
+	order.reverse();
+	Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{EdgeID, SimpleGraph},
+	};
+
+	#[test]
+	fn orders_acyclic_graphs() {
+		let (graph, nodes): (SimpleGraph<(), ()>, _) = test_graph(
+			5,
+			[(3, 1, ()), (1, 0, ()), (3, 4, ()), (4, 0, ()), (2, 4, ())],
+		);
+
+		let kahn = toposort_kahn(&graph).unwrap();
+		assert_eq!(kahn, vec![nodes[2], nodes[3], nodes[1], nodes[4], nodes[0]]);
+		let dfs = toposort_dfs(&graph).unwrap();
+		for order in [kahn, dfs] {
+			let position = |node| order.iter().position(|other| *other == node).unwrap();
+			assert_eq!(order.len(), nodes.len());
+			for (from, to) in [(3, 1), (1, 0), (3, 4), (4, 0), (2, 4)] {
+				assert!(position(nodes[from]) < position(nodes[to]));
+			}
+		}
+	}
+
+	#[test]
+	fn reports_cycles() {
+		let (graph, nodes): (SimpleGraph<(), ()>, _) =
+			test_graph(4, [(0, 1, ()), (1, 2, ()), (2, 3, ()), (3, 1, ())]);
+
+		let expected = vec![
+			EdgeID(nodes[1], nodes[2]),
+			EdgeID(nodes[2], nodes[3]),
+			EdgeID(nodes[3], nodes[1]),
+		];
+		for result in [toposort_kahn(&graph), toposort_dfs(&graph)] {
+			let Cycle(mut cycle) = result.unwrap_err();
+			let start = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+			cycle.rotate_left(start);
+			assert_eq!(cycle, expected);
+		}
+	}
+}
@@ -0,0 +1,223 @@
+use graph_traits::{GraphBase, GraphEdgeTo, GraphEdgesFrom};
+
+use std::{
+	collections::{HashSet, VecDeque},
+	hash::Hash,
+	iter::{once, Iterator},
+};
+
+/// A breadth-first traversal, yielding each reachable node exactly once
+///
+/// Nodes are visited in order of their distance from the closest root
+pub struct Bfs<'a, G>
+where
+	G: GraphBase,
+{
+	graph: &'a G,
+	queue: VecDeque<G::NodeID>,
+	visited: HashSet<G::NodeID>,
+}
+
+impl<'a, G> Bfs<'a, G>
+where
+	G: GraphEdgesFrom + GraphEdgeTo,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	/// Start a traversal from a single node
+	pub fn new(graph: &'a G, start: G::NodeID) -> Self { Self::with_roots(graph, once(start)) }
+
+	/// Start a traversal from several nodes at once, all of which are treated as distance zero
+	pub fn with_roots<I>(graph: &'a G, roots: I) -> Self
+	where
+		I: IntoIterator<Item = G::NodeID>,
+	{
+		let mut bfs = Self {
+			graph,
+			queue: VecDeque::new(),
+			visited: HashSet::new(),
+		};
+		for root in roots {
+			bfs.add_root(root);
+		}
+		bfs
+	}
+
+	/// Queue another root, which is ignored if it has already been visited
+	///
+	/// This can be used to continue the traversal into another part of the graph once the
+	/// iterator has been exhausted
+	pub fn add_root(&mut self, root: G::NodeID) {
+		if self.visited.insert(root) {
+			self.queue.push_back(root);
+		}
+	}
+
+	/// Whether the traversal has already reached a node
+	pub fn is_visited(&self, id: G::NodeID) -> bool { self.visited.contains(&id) }
+}
+
+impl<'a, G> Iterator for Bfs<'a, G>
+where
+	G: GraphEdgesFrom + GraphEdgeTo,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	type Item = G::NodeID;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let node = self.queue.pop_front()?;
+		for edge in self.graph.edges_from(node) {
+			let to = self.graph.edge_to(edge);
+			if self.visited.insert(to) {
+				self.queue.push_back(to);
+			}
+		}
+		Some(node)
+	}
+}
+
+/// An event produced by a depth-first traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DfsEvent<NodeID> {
+	/// The node has been reached for the first time, giving a pre-order
+	Discover(NodeID),
+	/// Every node reachable from the node has been discovered, giving a post-order
+	Finish(NodeID),
+}
+
+/// A depth-first traversal, yielding a `Discover` and a `Finish` event for each reachable node
+///
+/// Roots are explored one after another, skipping any which were reached from an earlier root
+pub struct Dfs<'a, G>
+where
+	G: GraphEdgesFrom,
+	G::EdgesFromOutput: IntoIterator,
+{
+	graph: &'a G,
+	roots: VecDeque<G::NodeID>,
+	stack: Vec<(G::NodeID, <G::EdgesFromOutput as IntoIterator>::IntoIter)>,
+	visited: HashSet<G::NodeID>,
+}
+
+impl<'a, G> Dfs<'a, G>
+where
+	G: GraphEdgesFrom + GraphEdgeTo,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	/// Start a traversal from a single node
+	pub fn new(graph: &'a G, start: G::NodeID) -> Self { Self::with_roots(graph, once(start)) }
+
+	/// Start a traversal which explores from each of the roots in turn
+	pub fn with_roots<I>(graph: &'a G, roots: I) -> Self
+	where
+		I: IntoIterator<Item = G::NodeID>,
+	{
+		Self {
+			graph,
+			roots: roots.into_iter().collect(),
+			stack: Vec::new(),
+			visited: HashSet::new(),
+		}
+	}
+
+	/// Queue another root, which is ignored if it has been visited by the time it is reached
+	pub fn add_root(&mut self, root: G::NodeID) {
+		self.roots.push_back(root);
+	}
+
+	/// Whether the traversal has already discovered a node
+	pub fn is_visited(&self, id: G::NodeID) -> bool { self.visited.contains(&id) }
+
+	/// Discover a node and push it onto the stack
+	fn discover(&mut self, node: G::NodeID) -> DfsEvent<G::NodeID> {
+		let edges = self.graph.edges_from(node).into_iter();
+		self.stack.push((node, edges));
+		DfsEvent::Discover(node)
+	}
+}
+
+impl<'a, G> Iterator for Dfs<'a, G>
+where
+	G: GraphEdgesFrom + GraphEdgeTo,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+{
+	type Item = DfsEvent<G::NodeID>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some((node, edges)) = self.stack.last_mut() {
+			let node = *node;
+			let graph = self.graph;
+			let visited = &mut self.visited;
+			let unvisited = edges
+				.map(|edge| graph.edge_to(edge))
+				.find(|to| visited.insert(*to));
+			return match unvisited {
+				Some(to) => Some(self.discover(to)),
+				None => {
+					self.stack.pop();
+					Some(DfsEvent::Finish(node))
+				}
+			};
+		}
+
+		// Move on to the next root which has not been visited yet
+		while let Some(root) = self.roots.pop_front() {
+			if self.visited.insert(root) {
+				return Some(self.discover(root));
+			}
+		}
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		algorithms::test_graph,
+		simple_graph::{NodeID, SimpleGraph},
+	};
+
+	/// A diamond `a -> b, a -> c, b -> d, c -> d` plus an unconnected node `e`
+	fn diamond() -> (SimpleGraph<(), ()>, Vec<NodeID>) {
+		test_graph(5, [(0, 1, ()), (0, 2, ()), (1, 3, ()), (2, 3, ())])
+	}
+
+	#[test]
+	fn bfs_visits_in_order_of_distance() {
+		let (graph, nodes) = diamond();
+		let order = Bfs::new(&graph, nodes[0]).collect::<Vec<_>>();
+		assert_eq!(order, vec![nodes[0], nodes[1], nodes[2], nodes[3]]);
+
+		let order = Bfs::with_roots(&graph, vec![nodes[4], nodes[2]]).collect::<Vec<_>>();
+		assert_eq!(order, vec![nodes[4], nodes[2], nodes[3]]);
+	}
+
+	#[test]
+	fn dfs_reports_discover_and_finish_events() {
+		let (graph, nodes) = diamond();
+		let mut dfs = Dfs::new(&graph, nodes[0]);
+		dfs.add_root(nodes[4]);
+		let events = dfs.collect::<Vec<_>>();
+
+		use DfsEvent::{Discover, Finish};
+		assert_eq!(
+			events,
+			vec![
+				Discover(nodes[0]),
+				Discover(nodes[1]),
+				Discover(nodes[3]),
+				Finish(nodes[3]),
+				Finish(nodes[1]),
+				Discover(nodes[2]),
+				Finish(nodes[2]),
+				Finish(nodes[0]),
+				Discover(nodes[4]),
+				Finish(nodes[4]),
+			]
+		);
+	}
+}
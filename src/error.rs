@@ -0,0 +1,44 @@
+use std::{error::Error, fmt};
+
+/// Errors returned by the fallible graph operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError {
+	/// The node is not in the graph
+	NodeNotFound,
+	/// The edge is not in the graph
+	EdgeNotFound,
+	/// The node cannot be removed while edges lead into or out of it
+	NodeHasEdges,
+}
+
+impl fmt::Display for GraphError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let message = match self {
+			GraphError::NodeNotFound => "node is not in the graph",
+			GraphError::EdgeNotFound => "edge is not in the graph",
+			GraphError::NodeHasEdges => "node still has edges leading into or out of it",
+		};
+		f.write_str(message)
+	}
+}
+
+impl Error for GraphError {}
+
+/// Error returned when a graph cannot be read from an external format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	/// The line of the input at which the problem was found, starting from 1
+	pub line: usize,
+	/// The column within the line, counted in characters and starting from 1
+	pub column: usize,
+	/// A description of the problem
+	pub message: String,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}: {}", self.line, self.column, self.message)
+	}
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,224 @@
+//! Compressed sparse row adjacency matrices, as used by numeric and scientific code
+//!
+//! Row `i` of the matrix holds the edges leaving the `i`th node, with their targets in `col_idx`
+//! and their weights in `values`, from `row_ptr[i]` up to `row_ptr[i + 1]`
+
+use graph_traits::{
+	GraphEdgeAddable, GraphEdgeIndexable, GraphEdgeTo, GraphEdgesFrom, GraphNodeAddable,
+};
+
+use crate::traits::GraphNodeIDs;
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A square sparse matrix in compressed sparse row form
+#[derive(Clone, Debug, PartialEq)]
+pub struct Csr {
+	row_ptr: Vec<usize>,
+	col_idx: Vec<usize>,
+	values: Vec<f64>,
+}
+
+impl Csr {
+	/// Panics if `row_ptr` is empty, does not start at zero, decreases or does not end at the length
+	/// of `col_idx`, if `col_idx` and `values` have different lengths, or if a column index is not
+	/// less than the number of rows
+	pub fn new(row_ptr: Vec<usize>, col_idx: Vec<usize>, values: Vec<f64>) -> Self {
+		assert!(row_ptr.first() == Some(&0), "row_ptr must start at zero");
+		assert!(
+			row_ptr.windows(2).all(|pair| pair[0] <= pair[1]),
+			"row_ptr must not decrease"
+		);
+		assert!(
+			row_ptr.last() == Some(&col_idx.len()),
+			"row_ptr must end at the number of entries"
+		);
+		assert!(
+			col_idx.len() == values.len(),
+			"col_idx and values must have the same length"
+		);
+		let size = row_ptr.len() - 1;
+		assert!(
+			col_idx.iter().all(|&column| column < size),
+			"column index out of range"
+		);
+		Self {
+			row_ptr,
+			col_idx,
+			values,
+		}
+	}
+
+	/// Build a matrix from `(row, column, value)` entries, keeping them in order within each row
+	///
+	/// Panics if a row or column is not less than `size`
+	pub fn from_entries<I>(size: usize, entries: I) -> Self
+	where
+		I: IntoIterator<Item = (usize, usize, f64)>,
+	{
+		Self::from_entries_in(vec![0; size + 1], entries.into_iter().collect())
+	}
+
+	/// Like `from_entries`, counting the entries of each row into `row_ptr`, which must hold
+	/// `size + 1` zeroes
+	pub(super) fn from_entries_in(
+		mut row_ptr: Vec<usize>,
+		entries: Vec<(usize, usize, f64)>,
+	) -> Self {
+		let size = row_ptr.len() - 1;
+		for (row, column, _) in &entries {
+			assert!(*row < size && *column < size, "index out of range");
+			row_ptr[row + 1] += 1;
+		}
+		for row in 0..size {
+			row_ptr[row + 1] += row_ptr[row];
+		}
+
+		// Place each entry after those already placed in its row, which keeps them in order
+		let mut next = row_ptr.clone();
+		let mut col_idx = vec![0; entries.len()];
+		let mut values = vec![0.0; entries.len()];
+		for (row, column, value) in entries {
+			col_idx[next[row]] = column;
+			values[next[row]] = value;
+			next[row] += 1;
+		}
+		Self {
+			row_ptr,
+			col_idx,
+			values,
+		}
+	}
+
+	/// Build the adjacency matrix of `graph`, weighting each edge with `weight`
+	///
+	/// Rows and columns follow the order of `node_ids`, which is returned alongside the matrix, and
+	/// the entries of each row are sorted by column. Undirected graphs give a symmetric matrix
+	pub fn from_graph<G, E, F>(graph: &G, mut weight: F) -> (Self, Vec<G::NodeID>)
+	where
+		G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo + GraphEdgeIndexable<E>,
+		G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+		G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+		G::NodeID: Eq + Hash,
+		F: FnMut(&E) -> f64,
+	{
+		let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+		let indices = nodes
+			.iter()
+			.enumerate()
+			.map(|(i, node)| (*node, i))
+			.collect::<HashMap<_, _>>();
+
+		let mut row_ptr = Vec::with_capacity(nodes.len() + 1);
+		let mut col_idx = Vec::new();
+		let mut values = Vec::new();
+		row_ptr.push(0);
+		for node in &nodes {
+			let mut row = graph
+				.edges_from(*node)
+				.into_iter()
+				.map(|edge| (indices[&graph.edge_to(edge)], weight(graph.edge(edge))))
+				.collect::<Vec<_>>();
+			row.sort_by_key(|(column, _)| *column);
+			for (column, value) in row {
+				col_idx.push(column);
+				values.push(value);
+			}
+			row_ptr.push(col_idx.len());
+		}
+		(
+			Self {
+				row_ptr,
+				col_idx,
+				values,
+			},
+			nodes,
+		)
+	}
+
+	/// Add a node to `graph` for every row and an edge for every entry, returning the added nodes
+	///
+	/// `node` creates the data for each node from its row, and `edge` creates the data for each
+	/// edge from its value. Repeated entries are all passed to `add_edge`
+	pub fn add_to_graph<G, N, E, FN, FE>(
+		&self,
+		graph: &mut G,
+		node: FN,
+		mut edge: FE,
+	) -> Vec<G::NodeID>
+	where
+		G: GraphNodeAddable<N> + GraphEdgeAddable<E>,
+		FN: FnMut(usize) -> N,
+		FE: FnMut(f64) -> E,
+	{
+		let ids = (0..self.size())
+			.map(node)
+			.map(|data| graph.add_node(data))
+			.collect::<Vec<_>>();
+		for (row, column, value) in self.entries() {
+			graph.add_edge(ids[row], ids[column], edge(value));
+		}
+		ids
+	}
+
+	/// The number of rows, which is also the number of columns
+	pub fn size(&self) -> usize { self.row_ptr.len() - 1 }
+
+	/// The number of stored entries
+	pub fn entry_count(&self) -> usize { self.col_idx.len() }
+
+	pub fn row_ptr(&self) -> &[usize] { &self.row_ptr }
+
+	pub fn col_idx(&self) -> &[usize] { &self.col_idx }
+
+	pub fn values(&self) -> &[f64] { &self.values }
+
+	/// The stored entries as `(row, column, value)`, in row order
+	pub fn entries(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+		self.row_ptr
+			.windows(2)
+			.enumerate()
+			.flat_map(move |(row, bounds)| {
+				(bounds[0]..bounds[1])
+					.map(move |entry| (row, self.col_idx[entry], self.values[entry]))
+			})
+	}
+
+	/// Split the matrix into its `row_ptr`, `col_idx` and `values` arrays
+	pub fn into_parts(self) -> (Vec<usize>, Vec<usize>, Vec<f64>) {
+		(self.row_ptr, self.col_idx, self.values)
+	}
+}
+
+impl Default for Csr {
+	/// An empty matrix with no rows
+	fn default() -> Self {
+		Self {
+			row_ptr: vec![0],
+			col_idx: Vec::new(),
+			values: Vec::new(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default_is_empty() {
+		let matrix = Csr::default();
+		assert_eq!(matrix.size(), 0);
+		assert_eq!(matrix.entries().count(), 0);
+		assert_eq!(matrix, Csr::new(vec![0], Vec::new(), Vec::new()));
+	}
+
+	#[test]
+	fn sorts_entries_into_rows() {
+		let matrix = Csr::from_entries(3, vec![(2, 0, 1.0), (0, 2, 2.0), (0, 1, 3.0)]);
+		assert_eq!(
+			matrix.into_parts(),
+			(vec![0, 2, 2, 3], vec![2, 1, 0], vec![2.0, 3.0, 1.0])
+		);
+	}
+}
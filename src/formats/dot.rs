@@ -0,0 +1,924 @@
+use graph_traits::{
+	GraphBase, GraphEdgeAddable, GraphEdgeIndexable, GraphEdgeTo, GraphEdgesFrom, GraphNodeAddable,
+	GraphNodeIndexable,
+};
+
+use super::{set_attribute, Attributes, Collected, Cursor, Imported, Location};
+use crate::{error::ParseError, traits::GraphNodeIDs};
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::{self, Write},
+	hash::Hash,
+	io,
+};
+
+type NodeCallback<'a, G, N, T> = Box<dyn Fn(<G as GraphBase>::NodeID, &N) -> T + 'a>;
+type EdgeCallback<'a, G, E, T> = Box<dyn Fn(<G as GraphBase>::EdgeID, &E) -> T + 'a>;
+
+/// Writes a graph in the Graphviz DOT language
+///
+/// Nodes are named `n0`, `n1`, ... in the order given by `node_ids`, and their labels, attributes
+/// and clusters are chosen with the builder methods
+///
+/// Every string given by the callbacks is escaped, so Graphviz displays it exactly as provided,
+/// and names and attribute keys which are DOT keywords are quoted
+pub struct Dot<'a, G, N, E>
+where
+	G: GraphBase,
+{
+	graph: &'a G,
+	directed: bool,
+	name: Option<String>,
+	node_label: Option<NodeCallback<'a, G, N, String>>,
+	edge_label: Option<EdgeCallback<'a, G, E, String>>,
+	node_attributes: Option<NodeCallback<'a, G, N, Attributes>>,
+	edge_attributes: Option<EdgeCallback<'a, G, E, Attributes>>,
+	cluster: Option<NodeCallback<'a, G, N, Option<String>>>,
+}
+
+impl<'a, G, N, E> Dot<'a, G, N, E>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo + GraphNodeIndexable<N> + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	G::EdgeID: Eq + Hash,
+{
+	/// Prepare to write a directed graph with no labels or attributes
+	pub fn new(graph: &'a G) -> Self {
+		Self {
+			graph,
+			directed: true,
+			name: None,
+			node_label: None,
+			edge_label: None,
+			node_attributes: None,
+			edge_attributes: None,
+			cluster: None,
+		}
+	}
+
+	/// Choose between a `digraph` with `->` edges and a `graph` with `--` edges
+	///
+	/// Edges reported from both of their endpoints by an undirected graph are only written once
+	pub fn directed(mut self, directed: bool) -> Self {
+		self.directed = directed;
+		self
+	}
+
+	/// Give the graph a name
+	pub fn name(mut self, name: &str) -> Self {
+		self.name = Some(name.to_owned());
+		self
+	}
+
+	/// Label every node with the result of `label`
+	pub fn node_label<F>(mut self, label: F) -> Self
+	where
+		F: Fn(G::NodeID, &N) -> String + 'a,
+	{
+		self.node_label = Some(Box::new(label));
+		self
+	}
+
+	/// Label every edge with the result of `label`
+	pub fn edge_label<F>(mut self, label: F) -> Self
+	where
+		F: Fn(G::EdgeID, &E) -> String + 'a,
+	{
+		self.edge_label = Some(Box::new(label));
+		self
+	}
+
+	/// Give every node the attributes returned by `attributes`, as key and value pairs
+	pub fn node_attributes<F>(mut self, attributes: F) -> Self
+	where
+		F: Fn(G::NodeID, &N) -> Vec<(String, String)> + 'a,
+	{
+		self.node_attributes = Some(Box::new(attributes));
+		self
+	}
+
+	/// Give every edge the attributes returned by `attributes`, as key and value pairs
+	pub fn edge_attributes<F>(mut self, attributes: F) -> Self
+	where
+		F: Fn(G::EdgeID, &E) -> Vec<(String, String)> + 'a,
+	{
+		self.edge_attributes = Some(Box::new(attributes));
+		self
+	}
+
+	/// Place nodes into cluster subgraphs labelled by the result of `cluster`
+	///
+	/// Nodes for which `cluster` returns `None` are placed outside of any cluster
+	pub fn cluster<F>(mut self, cluster: F) -> Self
+	where
+		F: Fn(G::NodeID, &N) -> Option<String> + 'a,
+	{
+		self.cluster = Some(Box::new(cluster));
+		self
+	}
+
+	/// Write the graph to a string or other `fmt::Write` implementor
+	pub fn to_dot<W>(&self, out: &mut W) -> fmt::Result
+	where
+		W: Write,
+	{
+		let graph = self.graph;
+		let nodes = graph.node_ids().into_iter().collect::<Vec<_>>();
+		let names = nodes
+			.iter()
+			.enumerate()
+			.map(|(i, node)| (*node, i))
+			.collect::<HashMap<_, _>>();
+
+		out.write_str(if self.directed { "digraph" } else { "graph" })?;
+		if let Some(name) = &self.name {
+			out.write_char(' ')?;
+			write_id(out, name)?;
+		}
+		out.write_str(" {\n")?;
+
+		// Group nodes by cluster, keeping the order in which clusters first appear
+		let mut clusters = Vec::<(String, Vec<G::NodeID>)>::new();
+		for node in &nodes {
+			let cluster = self
+				.cluster
+				.as_ref()
+				.and_then(|cluster| cluster(*node, graph.node(*node)));
+			match cluster {
+				Some(label) => match clusters.iter_mut().find(|(existing, _)| *existing == label) {
+					Some((_, members)) => members.push(*node),
+					None => clusters.push((label, vec![*node])),
+				},
+				None => self.write_node(out, "\t", names[node], *node)?,
+			}
+		}
+		for (i, (label, members)) in clusters.iter().enumerate() {
+			writeln!(out, "\tsubgraph cluster_{} {{", i)?;
+			out.write_str("\t\tlabel=")?;
+			write_quoted(out, label)?;
+			out.write_str(";\n")?;
+			for node in members {
+				self.write_node(out, "\t\t", names[node], *node)?;
+			}
+			out.write_str("\t}\n")?;
+		}
+
+		let connector = if self.directed { "->" } else { "--" };
+		let mut written = HashSet::new();
+		for node in &nodes {
+			for edge in graph.edges_from(*node) {
+				if !written.insert(edge) {
+					continue;
+				}
+				let data = graph.edge(edge);
+				let to = names[&graph.edge_to(edge)];
+				write!(out, "\tn{} {} n{}", names[node], connector, to)?;
+				let label = self.edge_label.as_ref().map(|label| label(edge, data));
+				let attributes = self
+					.edge_attributes
+					.as_ref()
+					.map(|attributes| attributes(edge, data));
+				write_attributes(out, label, attributes)?;
+			}
+		}
+
+		out.write_str("}\n")
+	}
+
+	/// Write the graph to a file or other `io::Write` implementor
+	pub fn to_dot_io<W>(&self, mut out: W) -> io::Result<()>
+	where
+		W: io::Write,
+	{
+		let mut dot = String::new();
+		self.to_dot(&mut dot)
+			.map_err(|_| io::Error::other("failed to format graph"))?;
+		out.write_all(dot.as_bytes())
+	}
+
+	fn write_node<W>(&self, out: &mut W, indent: &str, name: usize, node: G::NodeID) -> fmt::Result
+	where
+		W: Write,
+	{
+		let data = self.graph.node(node);
+		write!(out, "{}n{}", indent, name)?;
+		let label = self.node_label.as_ref().map(|label| label(node, data));
+		let attributes = self
+			.node_attributes
+			.as_ref()
+			.map(|attributes| attributes(node, data));
+		write_attributes(out, label, attributes)
+	}
+}
+
+impl<'a, G, N, E> fmt::Display for Dot<'a, G, N, E>
+where
+	G: GraphNodeIDs + GraphEdgesFrom + GraphEdgeTo + GraphNodeIndexable<N> + GraphEdgeIndexable<E>,
+	G::NodeIDsOutput: IntoIterator<Item = G::NodeID>,
+	G::EdgesFromOutput: IntoIterator<Item = G::EdgeID>,
+	G::NodeID: Eq + Hash,
+	G::EdgeID: Eq + Hash,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.to_dot(f) }
+}
+
+/// Write an optional label and attributes in brackets, finishing the statement
+fn write_attributes<W>(
+	out: &mut W,
+	label: Option<String>,
+	attributes: Option<Attributes>,
+) -> fmt::Result
+where
+	W: Write,
+{
+	let label = label.map(|label| ("label".to_owned(), label));
+	let mut attributes = label
+		.into_iter()
+		.chain(attributes.into_iter().flatten())
+		.peekable();
+	if attributes.peek().is_some() {
+		out.write_str(" [")?;
+		for (i, (key, value)) in attributes.enumerate() {
+			if i > 0 {
+				out.write_str(", ")?;
+			}
+			write_id(out, &key)?;
+			out.write_char('=')?;
+			write_quoted(out, &value)?;
+		}
+		out.write_char(']')?;
+	}
+	out.write_str(";\n")
+}
+
+/// Keywords of the DOT language, which are matched case-insensitively
+const KEYWORDS: [&str; 6] = ["graph", "digraph", "node", "edge", "subgraph", "strict"];
+
+/// Write a string as a bare identifier if it is one, otherwise quote it
+fn write_id<W>(out: &mut W, id: &str) -> fmt::Result
+where
+	W: Write,
+{
+	let mut chars = id.chars();
+	let is_plain = chars
+		.next()
+		.is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+		&& !KEYWORDS
+			.iter()
+			.any(|keyword| id.eq_ignore_ascii_case(keyword));
+	if is_plain {
+		out.write_str(id)
+	}
+	else {
+		write_quoted(out, id)
+	}
+}
+
+/// Write a string in double quotes, escaping anything which would end or alter it
+///
+/// Newlines are written as `\n`, which Graphviz displays as a line break, while any other
+/// character, including a carriage return, is written as is
+fn write_quoted<W>(out: &mut W, string: &str) -> fmt::Result
+where
+	W: Write,
+{
+	out.write_char('"')?;
+	for c in string.chars() {
+		match c {
+			'"' => out.write_str("\\\"")?,
+			'\\' => out.write_str("\\\\")?,
+			'\n' => out.write_str("\\n")?,
+			c => out.write_char(c)?,
+		}
+	}
+	out.write_char('"')
+}
+
+/// Read a graph in the Graphviz DOT language into `graph`, returning the ID given to each node name
+/// and whether the input was a `digraph`
+///
+/// `node` creates the data for each node from its name and attributes, in order of first
+/// appearance, and `edge` creates the data for each edge from its attributes. Attributes set with
+/// `node [...]` and `edge [...]` statements are included, and an edge statement with several
+/// targets or a subgraph as an endpoint adds an edge for every pair of nodes it joins
+///
+/// Ports and graph attributes are ignored. Quoted strings are returned with escaped quotes and
+/// line continuations removed, leaving any other escapes for the caller to interpret as Graphviz
+/// would
+///
+/// Subgraphs nested more than 128 deep are rejected with an error rather than risk overflowing
+/// the stack
+pub fn parse<G, N, E, FN, FE>(
+	input: &str,
+	graph: &mut G,
+	node: FN,
+	edge: FE,
+) -> Result<Imported<G::NodeID>, ParseError>
+where
+	G: GraphNodeAddable<N> + GraphEdgeAddable<E>,
+	FN: FnMut(&str, &[(String, String)]) -> N,
+	FE: FnMut(&[(String, String)]) -> E,
+{
+	let mut parser = Parser {
+		lexer: Lexer {
+			cursor: Cursor::new(input),
+			peeked: None,
+		},
+		directed: true,
+		depth: 0,
+		collected: Collected::default(),
+		node_defaults: Attributes::new(),
+		edge_defaults: Attributes::new(),
+	};
+	parser.graph()?;
+	Ok(Imported {
+		names: parser.collected.build(graph, node, edge),
+		directed: parser.directed,
+	})
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+	/// An identifier, numeral, quoted string or HTML string
+	ID {
+		text: String,
+		quoted: bool,
+	},
+	Punctuation(&'static str),
+	End,
+}
+
+impl Token {
+	fn is_keyword(&self, keyword: &str) -> bool {
+		matches!(self, Token::ID { text, quoted: false } if text.eq_ignore_ascii_case(keyword))
+	}
+
+	fn describe(&self) -> String {
+		match self {
+			Token::ID { text, .. } => format!("`{}`", text),
+			Token::Punctuation(punctuation) => format!("`{}`", punctuation),
+			Token::End => "end of input".to_owned(),
+		}
+	}
+}
+
+struct Lexer<'a> {
+	cursor: Cursor<'a>,
+	peeked: Option<(Location, Token)>,
+}
+
+impl<'a> Lexer<'a> {
+	fn peek(&mut self) -> Result<&Token, ParseError> {
+		if self.peeked.is_none() {
+			self.peeked = Some(self.lex()?);
+		}
+		Ok(&self.peeked.as_ref().unwrap().1)
+	}
+
+	fn next(&mut self) -> Result<(Location, Token), ParseError> {
+		match self.peeked.take() {
+			Some(peeked) => Ok(peeked),
+			None => self.lex(),
+		}
+	}
+
+	/// Skip whitespace, comments and preprocessor lines
+	fn skip_trivia(&mut self) -> Result<(), ParseError> {
+		loop {
+			self.cursor.bump_while(char::is_whitespace);
+			let location = self.cursor.location();
+			if self.cursor.eat("//") || (self.cursor.is_at_line_start() && self.cursor.eat("#")) {
+				self.cursor.bump_while(|c| c != '\n');
+			}
+			else if self.cursor.eat("/*") {
+				if self.cursor.bump_past("*/").is_none() {
+					return Err(location.error("unterminated comment"));
+				}
+			}
+			else {
+				return Ok(());
+			}
+		}
+	}
+
+	fn lex(&mut self) -> Result<(Location, Token), ParseError> {
+		self.skip_trivia()?;
+		let location = self.cursor.location();
+		let c = match self.cursor.peek() {
+			Some(c) => c,
+			None => return Ok((location, Token::End)),
+		};
+		let token = match c {
+			'{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' | '+' => {
+				self.cursor.bump();
+				Token::Punctuation(match c {
+					'{' => "{",
+					'}' => "}",
+					'[' => "[",
+					']' => "]",
+					';' => ";",
+					',' => ",",
+					'=' => "=",
+					':' => ":",
+					_ => "+",
+				})
+			}
+			'-' if self.cursor.eat("->") => Token::Punctuation("->"),
+			'-' if self.cursor.eat("--") => Token::Punctuation("--"),
+			'-' | '.' | '0'..='9' => {
+				let numeral = self
+					.cursor
+					.bump_while(|c| c == '-' || c == '.' || c.is_ascii_digit());
+				let digits = numeral.strip_prefix('-').unwrap_or(numeral);
+				let is_valid = !digits.contains('-')
+					&& digits.matches('.').count() <= 1
+					&& digits.contains(|c: char| c.is_ascii_digit());
+				if !is_valid {
+					return Err(location.error(format!("invalid numeral `{}`", numeral)));
+				}
+				Token::ID {
+					text: numeral.to_owned(),
+					quoted: false,
+				}
+			}
+			'"' => Token::ID {
+				text: self.quoted(location)?,
+				quoted: true,
+			},
+			'<' => Token::ID {
+				text: self.html(location)?,
+				quoted: true,
+			},
+			c if c.is_alphabetic() || c == '_' => {
+				let text = self.cursor.bump_while(|c| c.is_alphanumeric() || c == '_');
+				Token::ID {
+					text: text.to_owned(),
+					quoted: false,
+				}
+			}
+			c => return Err(location.error(format!("unexpected character `{}`", c))),
+		};
+		Ok((location, token))
+	}
+
+	fn quoted(&mut self, location: Location) -> Result<String, ParseError> {
+		self.cursor.bump();
+		let mut text = String::new();
+		loop {
+			match self.cursor.bump() {
+				None => return Err(location.error("unterminated string")),
+				Some('"') => return Ok(text),
+				Some('\\') if self.cursor.eat("\"") => text.push('"'),
+				Some('\\') if self.cursor.eat("\n") || self.cursor.eat("\r\n") => {}
+				Some(c) => text.push(c),
+			}
+		}
+	}
+
+	fn html(&mut self, location: Location) -> Result<String, ParseError> {
+		self.cursor.bump();
+		let mut text = String::new();
+		let mut depth = 0;
+		loop {
+			match self.cursor.bump() {
+				None => return Err(location.error("unterminated HTML string")),
+				Some('>') if depth == 0 => return Ok(text),
+				Some(c) => {
+					match c {
+						'<' => depth += 1,
+						'>' => depth -= 1,
+						_ => {}
+					}
+					text.push(c);
+				}
+			}
+		}
+	}
+}
+
+/// The deepest subgraphs may be nested, so that hostile input cannot overflow the stack
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+	lexer: Lexer<'a>,
+	directed: bool,
+	/// The number of subgraphs enclosing the current statement
+	depth: usize,
+	collected: Collected,
+	node_defaults: Attributes,
+	edge_defaults: Attributes,
+}
+
+impl<'a> Parser<'a> {
+	fn unexpected<T>(location: Location, found: &Token, expected: &str) -> Result<T, ParseError> {
+		Err(location.error(format!("expected {}, found {}", expected, found.describe())))
+	}
+
+	fn eat(&mut self, punctuation: &'static str) -> Result<bool, ParseError> {
+		let found = *self.lexer.peek()? == Token::Punctuation(punctuation);
+		if found {
+			self.lexer.next()?;
+		}
+		Ok(found)
+	}
+
+	fn expect(&mut self, punctuation: &'static str) -> Result<(), ParseError> {
+		let (location, token) = self.lexer.next()?;
+		if token != Token::Punctuation(punctuation) {
+			return Self::unexpected(location, &token, &format!("`{}`", punctuation));
+		}
+		Ok(())
+	}
+
+	/// Read an ID, joining quoted strings concatenated with `+`
+	fn id(&mut self) -> Result<String, ParseError> {
+		let (location, token) = self.lexer.next()?;
+		let (mut text, quoted) = match token {
+			Token::ID { text, quoted } => (text, quoted),
+			token => return Self::unexpected(location, &token, "an ID"),
+		};
+		while quoted && self.eat("+")? {
+			let (location, token) = self.lexer.next()?;
+			match token {
+				Token::ID {
+					text: next,
+					quoted: true,
+				} => text.push_str(&next),
+				token => return Self::unexpected(location, &token, "a quoted string"),
+			}
+		}
+		Ok(text)
+	}
+
+	fn graph(&mut self) -> Result<(), ParseError> {
+		if self.lexer.peek()?.is_keyword("strict") {
+			self.lexer.next()?;
+		}
+		let (location, token) = self.lexer.next()?;
+		if token.is_keyword("digraph") {
+			self.directed = true;
+		}
+		else if token.is_keyword("graph") {
+			self.directed = false;
+		}
+		else {
+			return Self::unexpected(location, &token, "`graph` or `digraph`");
+		}
+		if matches!(self.lexer.peek()?, Token::ID { .. }) {
+			self.id()?;
+		}
+		self.expect("{")?;
+		self.statements()?;
+		let (location, token) = self.lexer.next()?;
+		if token != Token::End {
+			return Self::unexpected(location, &token, "end of input");
+		}
+		Ok(())
+	}
+
+	/// Read statements up to and including the closing brace, returning the nodes they mention
+	fn statements(&mut self) -> Result<Vec<usize>, ParseError> {
+		let mut mentioned = Vec::new();
+		while !self.eat("}")? {
+			self.statement(&mut mentioned)?;
+			self.eat(";")?;
+		}
+		Ok(mentioned)
+	}
+
+	fn statement(&mut self, mentioned: &mut Vec<usize>) -> Result<(), ParseError> {
+		let token = self.lexer.peek()?.clone();
+		if token.is_keyword("graph") || token.is_keyword("node") || token.is_keyword("edge") {
+			self.lexer.next()?;
+			if *self.lexer.peek()? != Token::Punctuation("[") {
+				let (location, token) = self.lexer.next()?;
+				return Self::unexpected(location, &token, "`[`");
+			}
+			let attributes = self.attributes()?;
+			let defaults = if token.is_keyword("node") {
+				&mut self.node_defaults
+			}
+			else if token.is_keyword("edge") {
+				&mut self.edge_defaults
+			}
+			else {
+				return Ok(());
+			};
+			for (key, value) in attributes {
+				set_attribute(defaults, key, value);
+			}
+			return Ok(());
+		}
+		if token == Token::Punctuation("{") || token.is_keyword("subgraph") {
+			let nodes = self.subgraph(mentioned)?;
+			return self.edges(nodes, mentioned);
+		}
+		if let Token::ID { .. } = token {
+			let name = self.id()?;
+			if self.eat("=")? {
+				self.id()?;
+				return Ok(());
+			}
+			let node = self.node(&name, mentioned)?;
+			if let Token::Punctuation("->" | "--") = self.lexer.peek()? {
+				return self.edges(vec![node], mentioned);
+			}
+			for (key, value) in self.attributes()? {
+				set_attribute(self.collected.attributes_mut(node), key, value);
+			}
+			return Ok(());
+		}
+		let (location, token) = self.lexer.next()?;
+		Self::unexpected(location, &token, "a statement")
+	}
+
+	/// Finish reading a node ID after its name, skipping any port
+	fn node(&mut self, name: &str, mentioned: &mut Vec<usize>) -> Result<usize, ParseError> {
+		while self.eat(":")? {
+			self.id()?;
+		}
+		let node = self.collected.node(name, &self.node_defaults);
+		mentioned.push(node);
+		Ok(node)
+	}
+
+	fn subgraph(&mut self, mentioned: &mut Vec<usize>) -> Result<Vec<usize>, ParseError> {
+		if self.lexer.peek()?.is_keyword("subgraph") {
+			self.lexer.next()?;
+			if matches!(self.lexer.peek()?, Token::ID { .. }) {
+				self.id()?;
+			}
+		}
+		let (location, token) = self.lexer.next()?;
+		if token != Token::Punctuation("{") {
+			return Self::unexpected(location, &token, "`{`");
+		}
+		if self.depth == MAX_DEPTH {
+			return Err(location.error(format!("subgraphs nested more than {} deep", MAX_DEPTH)));
+		}
+		let node_defaults = self.node_defaults.clone();
+		let edge_defaults = self.edge_defaults.clone();
+		self.depth += 1;
+		let mut nodes = self.statements()?;
+		self.depth -= 1;
+		self.node_defaults = node_defaults;
+		self.edge_defaults = edge_defaults;
+
+		let mut seen = HashSet::new();
+		nodes.retain(|node| seen.insert(*node));
+		mentioned.extend(&nodes);
+		Ok(nodes)
+	}
+
+	/// Read the rest of an edge statement whose first endpoint has been read
+	fn edges(&mut self, first: Vec<usize>, mentioned: &mut Vec<usize>) -> Result<(), ParseError> {
+		let mut endpoints = vec![first];
+		while let Token::Punctuation("->" | "--") = self.lexer.peek()? {
+			let (location, token) = self.lexer.next()?;
+			if token != Token::Punctuation(if self.directed { "->" } else { "--" }) {
+				let kind = if self.directed {
+					"directed"
+				}
+				else {
+					"undirected"
+				};
+				return Err(location.error(format!("{} used in {} graph", token.describe(), kind)));
+			}
+			let endpoint = match self.lexer.peek()?.clone() {
+				Token::Punctuation("{") => self.subgraph(mentioned)?,
+				token if token.is_keyword("subgraph") => self.subgraph(mentioned)?,
+				Token::ID { .. } => {
+					let name = self.id()?;
+					vec![self.node(&name, mentioned)?]
+				}
+				_ => {
+					let (location, token) = self.lexer.next()?;
+					return Self::unexpected(location, &token, "a node or subgraph");
+				}
+			};
+			endpoints.push(endpoint);
+		}
+
+		let mut attributes = self.edge_defaults.clone();
+		for (key, value) in self.attributes()? {
+			set_attribute(&mut attributes, key, value);
+		}
+		for pair in endpoints.windows(2) {
+			for &from in &pair[0] {
+				for &to in &pair[1] {
+					self.collected.add_edge(from, to, attributes.clone());
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Read any number of bracketed attribute lists
+	fn attributes(&mut self) -> Result<Attributes, ParseError> {
+		let mut attributes = Attributes::new();
+		while self.eat("[")? {
+			while !self.eat("]")? {
+				let key = self.id()?;
+				self.expect("=")?;
+				let value = self.id()?;
+				set_attribute(&mut attributes, key, value);
+				if !self.eat(",")? {
+					self.eat(";")?;
+				}
+			}
+		}
+		Ok(attributes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		simple_graph::{EdgeID, NodeID, SimpleGraph},
+		undirected_graph::UndirectedGraph,
+	};
+
+	#[test]
+	fn writes_labels_attributes_and_clusters() {
+		let mut graph = SimpleGraph::<&str, i32>::new();
+		let a = graph.add_node("a \"quoted\"");
+		let b = graph.add_node("b\\c");
+		let c = graph.add_node("line\nbreak\r");
+		graph.add_edge(a, b, 1);
+		graph.add_edge(b, c, 2);
+
+		let dot = Dot::new(&graph)
+			.node_label(|_, name: &&str| name.to_string())
+			.edge_attributes(|_, weight: &i32| vec![("weight".to_owned(), weight.to_string())])
+			.cluster(|_, name| name.starts_with('l').then(|| "lines".to_owned()))
+			.to_string();
+		assert_eq!(
+			dot,
+			"digraph {\n\
+			\tn0 [label=\"a \\\"quoted\\\"\"];\n\
+			\tn1 [label=\"b\\\\c\"];\n\
+			\tsubgraph cluster_0 {\n\
+			\t\tlabel=\"lines\";\n\
+			\t\tn2 [label=\"line\\nbreak\r\"];\n\
+			\t}\n\
+			\tn0 -> n1 [weight=\"1\"];\n\
+			\tn1 -> n2 [weight=\"2\"];\n\
+			}\n"
+		);
+	}
+
+	#[test]
+	fn quotes_keywords() {
+		let mut graph = SimpleGraph::<(), ()>::new();
+		graph.add_node(());
+		let dot = Dot::new(&graph)
+			.name("Node")
+			.node_attributes(|_, _| vec![("edge".to_owned(), "x".to_owned())])
+			.to_string();
+		assert_eq!(dot, "digraph \"Node\" {\n\tn0 [\"edge\"=\"x\"];\n}\n");
+	}
+
+	#[test]
+	fn writes_undirected_edges_once() {
+		let mut graph = UndirectedGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		graph.add_edge(a, b, ());
+
+		let mut out = Vec::new();
+		Dot::new(&graph)
+			.directed(false)
+			.to_dot_io(&mut out)
+			.unwrap();
+		assert_eq!(
+			String::from_utf8(out).unwrap(),
+			"graph {\n\tn0;\n\tn1;\n\tn0 -- n1;\n}\n"
+		);
+	}
+
+	type Parsed = SimpleGraph<(String, Attributes), Attributes>;
+
+	fn parse_str(input: &str) -> Result<(Parsed, Imported<NodeID>), ParseError> {
+		let mut graph = Parsed::new();
+		let imported = parse(
+			input,
+			&mut graph,
+			|name, attributes| (name.to_owned(), attributes.to_vec()),
+			|attributes| attributes.to_vec(),
+		)?;
+		Ok((graph, imported))
+	}
+
+	fn attribute<'a>(attributes: &'a Attributes, key: &str) -> Option<&'a str> {
+		attributes
+			.iter()
+			.find(|(existing, _)| existing == key)
+			.map(|(_, value)| value.as_str())
+	}
+
+	#[test]
+	fn expands_subgraph_endpoints_and_applies_defaults() {
+		let input = r#"
+			strict digraph "G" {
+				node [shape=box]
+				a [label="A \"x\""; color=red]
+				a -> b -> {c; subgraph s { node [shape=circle] d }} [weight=3]
+				e:port -> a
+				f [label="con" + "cat"]
+			}
+		"#;
+		let (graph, imported) = parse_str(input).unwrap();
+		let names = &imported.names;
+		assert!(imported.directed);
+		assert_eq!(graph.node_count(), 6);
+		assert_eq!(graph.edge_count(), 4);
+
+		let a = &graph.node(names["a"]).1;
+		assert_eq!(attribute(a, "label"), Some("A \"x\""));
+		assert_eq!(attribute(a, "shape"), Some("box"));
+		assert_eq!(
+			attribute(&graph.node(names["d"]).1, "shape"),
+			Some("circle")
+		);
+		assert_eq!(attribute(&graph.node(names["e"]).1, "shape"), Some("box"));
+		assert_eq!(
+			attribute(&graph.node(names["f"]).1, "label"),
+			Some("concat")
+		);
+
+		let to_d = EdgeID(names["b"], names["d"]);
+		assert_eq!(
+			attribute(graph.try_edge(to_d).unwrap(), "weight"),
+			Some("3")
+		);
+		assert!(graph.try_edge(EdgeID(names["b"], names["c"])).is_ok());
+		assert!(graph
+			.try_edge(EdgeID(names["e"], names["a"]))
+			.unwrap()
+			.is_empty());
+	}
+
+	#[test]
+	fn reports_undirected_graphs() {
+		let (graph, imported) = parse_str("graph { a -- b }").unwrap();
+		assert!(!imported.directed);
+		assert_eq!(graph.edge_count(), 1);
+	}
+
+	#[test]
+	fn reads_back_written_keywords() {
+		let mut graph = SimpleGraph::<&str, ()>::new();
+		graph.add_node("node");
+		let dot = Dot::new(&graph)
+			.name("graph")
+			.node_label(|_, name: &&str| name.to_string())
+			.to_string();
+		let (parsed, _) = parse_str(&dot).unwrap();
+		let id = parsed.node_ids().next().unwrap();
+		assert_eq!(attribute(&parsed.node(id).1, "label"), Some("node"));
+	}
+
+	#[test]
+	fn reports_error_locations() {
+		let cases = [
+			(
+				"digraph {\n\ta -- b\n}",
+				2,
+				4,
+				"`--` used in directed graph",
+			),
+			("graph {\n  a -> b }", 2, 5, "`->` used in undirected graph"),
+			("digraph { a [x] }", 1, 15, "expected `=`, found `]`"),
+			("digraph {\n  \"abc", 2, 3, "unterminated string"),
+			("digraph { a } b", 1, 15, "expected end of input, found `b`"),
+			("foo {}", 1, 1, "expected `graph` or `digraph`, found `foo`"),
+			("digraph { /* x", 1, 11, "unterminated comment"),
+			("digraph { 1.2.3 }", 1, 11, "invalid numeral `1.2.3`"),
+		];
+		for (input, line, column, message) in cases {
+			let error = parse_str(input).unwrap_err();
+			assert_eq!(
+				(error.line, error.column, error.message.as_str()),
+				(line, column, message)
+			);
+		}
+	}
+
+	#[test]
+	fn limits_subgraph_nesting() {
+		let nested = |depth| format!("digraph {{{}{}}}", "{".repeat(depth), "}".repeat(depth));
+		assert!(parse_str(&nested(MAX_DEPTH)).is_ok());
+
+		let error = parse_str(&nested(MAX_DEPTH + 1)).unwrap_err();
+		assert_eq!(
+			(error.line, error.column, error.message.as_str()),
+			(1, 10 + MAX_DEPTH, "subgraphs nested more than 128 deep")
+		);
+		assert!(parse_str(&nested(100_000)).is_err());
+	}
+}
@@ -0,0 +1,119 @@
+//! Plain text edge lists
+//!
+//! Every line names the source and target nodes of one edge, separated by whitespace, optionally
+//! followed by further fields such as a weight. A line naming a single node adds that node without
+//! any edges. Blank lines and anything after a `#` are ignored
+
+use graph_traits::{GraphEdgeAddable, GraphNodeAddable};
+
+use crate::error::ParseError;
+
+use std::collections::HashMap;
+
+/// Read an edge list into `graph`, returning the ID given to each node name
+///
+/// `node` creates the data for each node from its name, in order of first appearance. `edge`
+/// creates the data for each edge from the fields after the two node names, and its error message
+/// is reported at the location of the first of those fields
+pub fn parse<G, N, E, FN, FE>(
+	input: &str,
+	graph: &mut G,
+	mut node: FN,
+	mut edge: FE,
+) -> Result<HashMap<String, G::NodeID>, ParseError>
+where
+	G: GraphNodeAddable<N> + GraphEdgeAddable<E>,
+	FN: FnMut(&str) -> N,
+	FE: FnMut(&[&str]) -> Result<E, String>,
+{
+	let mut names = Vec::new();
+	let mut indices = HashMap::new();
+	let mut index = |name: &str| {
+		*indices.entry(name.to_owned()).or_insert_with(|| {
+			names.push(name.to_owned());
+			names.len() - 1
+		})
+	};
+
+	let mut edges = Vec::new();
+	for (line_index, line) in input.lines().enumerate() {
+		let content = line.split('#').next().unwrap_or_default();
+		let fields = content.split_whitespace().collect::<Vec<_>>();
+		match fields.as_slice() {
+			[] => {}
+			[name] => {
+				index(name);
+			}
+			[from, to, rest @ ..] => {
+				let from = index(from);
+				let to = index(to);
+				let data = edge(rest).map_err(|message| {
+					let field = rest.first().copied().unwrap_or(content.trim_end());
+					let offset = field.as_ptr() as usize - line.as_ptr() as usize;
+					let column = line[..offset].chars().count() + 1;
+					let column = if rest.is_empty() {
+						column + field.chars().count()
+					}
+					else {
+						column
+					};
+					ParseError {
+						line: line_index + 1,
+						column,
+						message,
+					}
+				})?;
+				edges.push((from, to, data));
+			}
+		}
+	}
+
+	let ids = names
+		.iter()
+		.map(|name| graph.add_node(node(name)))
+		.collect::<Vec<_>>();
+	for (from, to, data) in edges {
+		graph.add_edge(ids[from], ids[to], data);
+	}
+	Ok(names.into_iter().zip(ids).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::simple_graph::{EdgeID, SimpleGraph};
+	use graph_traits::GraphNodeIndexable;
+
+	fn weight(fields: &[&str]) -> Result<f64, String> {
+		let field = fields.first().ok_or_else(|| "missing weight".to_owned())?;
+		field
+			.parse()
+			.map_err(|_| format!("`{}` is not a weight", field))
+	}
+
+	#[test]
+	fn reads_edges_isolated_nodes_and_comments() {
+		let mut graph = SimpleGraph::<String, f64>::new();
+		let input = "# weighted\na b 1.5\n\nb c 2 # trailing\nd\n";
+		let names = parse(input, &mut graph, str::to_owned, weight).unwrap();
+		assert_eq!(graph.node_count(), 4);
+		assert_eq!(graph.edge_count(), 2);
+		assert_eq!(graph.node(names["d"]), "d");
+		assert_eq!(graph.try_edge(EdgeID(names["b"], names["c"])), Ok(&2.0));
+	}
+
+	#[test]
+	fn reports_bad_weights_at_their_field() {
+		let mut graph = SimpleGraph::<String, f64>::new();
+		let error = parse("a b 1\n  a  c  x\n", &mut graph, str::to_owned, weight).unwrap_err();
+		assert_eq!((error.line, error.column), (2, 9));
+		assert_eq!(error.message, "`x` is not a weight");
+
+		let error = parse("a b\n", &mut graph, str::to_owned, weight).unwrap_err();
+		assert_eq!(
+			(error.line, error.column, error.message.as_str()),
+			(1, 4, "missing weight")
+		);
+		assert!(graph.is_empty());
+	}
+}
@@ -0,0 +1,542 @@
+//! GraphML, as written by Gephi, yEd and other graph tools
+//!
+//! Nodes are named by their `id` attributes, and `data` values are given as attributes named after
+//! the `attr.name` of their key, or the key's `id` when it has no name. Key defaults are applied to
+//! nodes and edges without a value of their own. Nested graphs are flattened into one, and ports
+//! and data nested inside other elements are ignored
+//!
+//! The direction of the graph is taken from the `edgedefault` of the outermost `graph` element. An
+//! edge whose own `directed` attribute, or the `edgedefault` of a nested graph, gives it the other
+//! direction has a `directed` attribute of `true` or `false` added to its attributes
+
+use graph_traits::{GraphEdgeAddable, GraphNodeAddable};
+
+use super::{set_attribute, Attributes, Collected, Cursor, Imported, Location};
+use crate::error::ParseError;
+
+use std::collections::{HashMap, HashSet};
+
+/// Read a GraphML document into `graph`, returning the ID given to each node name and whether the
+/// graph is directed
+///
+/// `node` creates the data for each node from its name and attributes, in document order, and
+/// `edge` creates the data for each edge from its attributes
+pub fn parse<G, N, E, FN, FE>(
+	input: &str,
+	graph: &mut G,
+	node: FN,
+	edge: FE,
+) -> Result<Imported<G::NodeID>, ParseError>
+where
+	G: GraphNodeAddable<N> + GraphEdgeAddable<E>,
+	FN: FnMut(&str, &[(String, String)]) -> N,
+	FE: FnMut(&[(String, String)]) -> E,
+{
+	let mut reader = Reader {
+		cursor: Cursor::new(input),
+		open: Vec::new(),
+		pending_end: false,
+	};
+	let mut keys = Vec::<Key>::new();
+	let mut key_indices = HashMap::new();
+	let mut collected = Collected::default();
+	let mut declared = HashSet::new();
+	let mut edges = Vec::<PendingEdge>::new();
+	let mut frames = Vec::new();
+	let mut directed = None;
+
+	loop {
+		let (location, event) = reader.next()?;
+		match event {
+			Event::Start { name, attributes } => {
+				let get = |attribute: &str| {
+					attributes
+						.iter()
+						.find(|(key, _)| key == attribute)
+						.map(|(_, value)| value.clone())
+				};
+				let require = |attribute: &str| {
+					get(attribute).ok_or_else(|| {
+						location.error(format!("`<{}>` is missing attribute `{}`", name, attribute))
+					})
+				};
+				let direction =
+					|attribute: &str, directed: &str, undirected: &str| match get(attribute) {
+						Some(value) if value == directed => Ok(Some(true)),
+						Some(value) if value == undirected => Ok(Some(false)),
+						Some(value) => {
+							Err(location.error(format!("invalid {} `{}`", attribute, value)))
+						}
+						None => Ok(None),
+					};
+				let element = local_name(&name);
+				if frames.is_empty() && element != "graphml" {
+					return Err(location.error(format!("expected `<graphml>`, found `<{}>`", name)));
+				}
+				let frame = match (element, frames.last()) {
+					("key", _) => {
+						let id = require("id")?;
+						key_indices.insert(id.clone(), keys.len());
+						keys.push(Key {
+							name: get("attr.name").unwrap_or(id),
+							domain: get("for").unwrap_or_else(|| "all".to_owned()),
+							default: None,
+						});
+						Frame::Key(keys.len() - 1)
+					}
+					("default", Some(Frame::Key(key))) => Frame::Default(*key, String::new()),
+					("graph", _) => {
+						let graph_directed = direction("edgedefault", "directed", "undirected")?;
+						let graph_directed = graph_directed.unwrap_or(true);
+						directed.get_or_insert(graph_directed);
+						Frame::Graph(graph_directed)
+					}
+					("node", _) => {
+						let id = require("id")?;
+						if !declared.insert(id.clone()) {
+							return Err(
+								location.error(format!("node `{}` is declared more than once", id))
+							);
+						}
+						Frame::Node(collected.node(&id, &Attributes::new()))
+					}
+					("edge", _) => {
+						let graph_directed = frames.iter().rev().find_map(|frame| match frame {
+							Frame::Graph(directed) => Some(*directed),
+							_ => None,
+						});
+						let edge_directed = direction("directed", "true", "false")?;
+						edges.push(PendingEdge {
+							location,
+							source: require("source")?,
+							target: require("target")?,
+							directed: edge_directed.or(graph_directed).unwrap_or(true),
+							attributes: Attributes::new(),
+						});
+						Frame::Edge(edges.len() - 1)
+					}
+					("hyperedge", _) => return Err(location.error("hyperedges are not supported")),
+					("data", Some(Frame::Node(_) | Frame::Edge(_))) => {
+						let id = require("key")?;
+						let key = *key_indices.get(&id).ok_or_else(|| {
+							location.error(format!("data refers to undeclared key `{}`", id))
+						})?;
+						let target = match frames.last() {
+							Some(Frame::Node(node)) => Target::Node(*node),
+							Some(Frame::Edge(edge)) => Target::Edge(*edge),
+							_ => unreachable!(),
+						};
+						Frame::Data(key, target, String::new())
+					}
+					_ => Frame::Other,
+				};
+				frames.push(frame);
+			}
+			Event::Text(text) => {
+				if let Some(Frame::Default(_, value) | Frame::Data(_, _, value)) = frames.last_mut()
+				{
+					value.push_str(&text);
+				}
+			}
+			Event::End => match frames.pop() {
+				Some(Frame::Default(key, value)) => keys[key].default = Some(value),
+				Some(Frame::Data(key, target, value)) => {
+					let attributes = match target {
+						Target::Node(node) => collected.attributes_mut(node),
+						Target::Edge(edge) => &mut edges[edge].attributes,
+					};
+					set_attribute(attributes, keys[key].name.clone(), value);
+				}
+				_ => {}
+			},
+			Event::Eof => break,
+		}
+	}
+
+	for attributes in collected.node_attributes_mut() {
+		apply_defaults(attributes, &keys, "node");
+	}
+	let directed = directed.unwrap_or(true);
+	for mut edge in edges {
+		let endpoint = |name: &str| {
+			collected.index(name).ok_or_else(|| {
+				edge.location
+					.error(format!("edge refers to undeclared node `{}`", name))
+			})
+		};
+		let source = endpoint(&edge.source)?;
+		let target = endpoint(&edge.target)?;
+		apply_defaults(&mut edge.attributes, &keys, "edge");
+		if edge.directed != directed {
+			set_attribute(
+				&mut edge.attributes,
+				"directed".to_owned(),
+				edge.directed.to_string(),
+			);
+		}
+		collected.add_edge(source, target, edge.attributes);
+	}
+	Ok(Imported {
+		names: collected.build(graph, node, edge),
+		directed,
+	})
+}
+
+struct Key {
+	name: String,
+	/// The kind of element the key applies to
+	domain: String,
+	default: Option<String>,
+}
+
+struct PendingEdge {
+	location: Location,
+	source: String,
+	target: String,
+	directed: bool,
+	attributes: Attributes,
+}
+
+enum Target {
+	Node(usize),
+	Edge(usize),
+}
+
+/// The meaning of an open element
+enum Frame {
+	Key(usize),
+	Default(usize, String),
+	/// A graph and whether its edges are directed by default
+	Graph(bool),
+	Node(usize),
+	Edge(usize),
+	Data(usize, Target, String),
+	Other,
+}
+
+/// Give `attributes` the defaults of every key applying to `domain` which it has no value for
+fn apply_defaults(attributes: &mut Attributes, keys: &[Key], domain: &str) {
+	for key in keys {
+		if key.domain != domain && key.domain != "all" {
+			continue;
+		}
+		if let Some(default) = &key.default {
+			if !attributes.iter().any(|(name, _)| *name == key.name) {
+				attributes.push((key.name.clone(), default.clone()));
+			}
+		}
+	}
+}
+
+/// The name of an element without its namespace prefix
+fn local_name(name: &str) -> &str { name.rsplit(':').next().unwrap_or(name) }
+
+enum Event {
+	Start {
+		name: String,
+		attributes: Attributes,
+	},
+	End,
+	Text(String),
+	Eof,
+}
+
+/// Reads the elements and text of an XML document, checking that elements are properly nested
+struct Reader<'a> {
+	cursor: Cursor<'a>,
+	open: Vec<String>,
+	/// Whether the last element started was self-closing, so should be ended straight away
+	pending_end: bool,
+}
+
+impl<'a> Reader<'a> {
+	fn next(&mut self) -> Result<(Location, Event), ParseError> {
+		loop {
+			let location = self.cursor.location();
+			if self.pending_end {
+				self.pending_end = false;
+				return Ok((location, Event::End));
+			}
+			if self.cursor.peek().is_none() {
+				return match self.open.last() {
+					Some(name) => Err(location.error(format!("`<{}>` is never closed", name))),
+					None => Ok((location, Event::Eof)),
+				};
+			}
+
+			if self.cursor.eat("<!--") {
+				self.skip_past("-->", location, "unterminated comment")?;
+			}
+			else if self.cursor.eat("<?") {
+				self.skip_past("?>", location, "unterminated processing instruction")?;
+			}
+			else if self.cursor.eat("<![CDATA[") {
+				let text = self.skip_past("]]>", location, "unterminated CDATA section")?;
+				return Ok((location, Event::Text(text.to_owned())));
+			}
+			else if self.cursor.eat("<!") {
+				let mut depth = 0;
+				loop {
+					match self.cursor.bump() {
+						None => return Err(location.error("unterminated declaration")),
+						Some('[') => depth += 1,
+						Some(']') => depth -= 1,
+						Some('>') if depth == 0 => break,
+						Some(_) => {}
+					}
+				}
+			}
+			else if self.cursor.eat("</") {
+				let name = self.name()?;
+				self.cursor.bump_while(char::is_whitespace);
+				self.expect('>')?;
+				match self.open.pop() {
+					Some(open) if open == name => return Ok((location, Event::End)),
+					Some(open) => {
+						return Err(
+							location.error(format!("expected `</{}>`, found `</{}>`", open, name))
+						);
+					}
+					None => return Err(location.error(format!("unexpected `</{}>`", name))),
+				}
+			}
+			else if self.cursor.eat("<") {
+				return self.start(location);
+			}
+			else {
+				let text = self.cursor.bump_while(|c| c != '<');
+				return Ok((location, Event::Text(decode(text, location)?)));
+			}
+		}
+	}
+
+	fn start(&mut self, location: Location) -> Result<(Location, Event), ParseError> {
+		let name = self.name()?;
+		let mut attributes = Attributes::new();
+		loop {
+			self.cursor.bump_while(char::is_whitespace);
+			if self.cursor.eat("/>") {
+				self.pending_end = true;
+				break;
+			}
+			if self.cursor.eat(">") {
+				self.open.push(name.clone());
+				break;
+			}
+			let key = self.name()?;
+			self.cursor.bump_while(char::is_whitespace);
+			self.expect('=')?;
+			self.cursor.bump_while(char::is_whitespace);
+			let value_location = self.cursor.location();
+			let quote = match self.cursor.bump() {
+				Some(quote @ ('"' | '\'')) => quote,
+				_ => {
+					return Err(
+						value_location.error(format!("expected a quoted value for `{}`", key))
+					)
+				}
+			};
+			let value = self.cursor.bump_while(|c| c != quote && c != '<');
+			if self.cursor.bump() != Some(quote) {
+				return Err(value_location.error(format!("unterminated value for `{}`", key)));
+			}
+			attributes.push((key, decode(value, value_location)?));
+		}
+		Ok((location, Event::Start { name, attributes }))
+	}
+
+	fn name(&mut self) -> Result<String, ParseError> {
+		let location = self.cursor.location();
+		let name = self
+			.cursor
+			.bump_while(|c| !c.is_whitespace() && !matches!(c, '<' | '>' | '/' | '=' | '"' | '\''));
+		if name.is_empty() {
+			return Err(location.error("expected a name"));
+		}
+		Ok(name.to_owned())
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+		let location = self.cursor.location();
+		match self.cursor.bump() {
+			Some(c) if c == expected => Ok(()),
+			Some(c) => Err(location.error(format!("expected `{}`, found `{}`", expected, c))),
+			None => Err(location.error(format!("expected `{}`, found end of input", expected))),
+		}
+	}
+
+	fn skip_past(
+		&mut self,
+		terminator: &str,
+		location: Location,
+		message: &str,
+	) -> Result<&'a str, ParseError> {
+		self.cursor
+			.bump_past(terminator)
+			.ok_or_else(|| location.error(message))
+	}
+}
+
+/// Replace entity and character references with the characters they stand for
+fn decode(raw: &str, location: Location) -> Result<String, ParseError> {
+	let mut decoded = String::new();
+	let mut rest = raw;
+	while let Some(start) = rest.find('&') {
+		decoded.push_str(&rest[..start]);
+		rest = &rest[start..];
+		let end = rest
+			.find(';')
+			.ok_or_else(|| location.error("unterminated entity reference"))?;
+		let entity = &rest[1..end];
+		let c = match entity {
+			"lt" => Some('<'),
+			"gt" => Some('>'),
+			"amp" => Some('&'),
+			"quot" => Some('"'),
+			"apos" => Some('\''),
+			_ => {
+				let code = match entity
+					.strip_prefix("#x")
+					.or_else(|| entity.strip_prefix("#X"))
+				{
+					Some(hex) => u32::from_str_radix(hex, 16).ok(),
+					None => entity
+						.strip_prefix('#')
+						.and_then(|decimal| decimal.parse().ok()),
+				};
+				code.and_then(char::from_u32)
+			}
+		};
+		let c = c.ok_or_else(|| location.error(format!("unknown entity `&{};`", entity)))?;
+		decoded.push(c);
+		rest = &rest[end + 1..];
+	}
+	decoded.push_str(rest);
+	Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::simple_graph::{EdgeID, NodeID, SimpleGraph};
+	use graph_traits::GraphNodeIndexable;
+
+	type Parsed = SimpleGraph<Attributes, Attributes>;
+
+	fn parse_str(input: &str) -> Result<(Parsed, Imported<NodeID>), ParseError> {
+		let mut graph = Parsed::new();
+		let imported = parse(
+			input,
+			&mut graph,
+			|_, attributes| attributes.to_vec(),
+			|attributes| attributes.to_vec(),
+		)?;
+		Ok((graph, imported))
+	}
+
+	fn attribute<'a>(attributes: &'a Attributes, key: &str) -> Option<&'a str> {
+		attributes
+			.iter()
+			.find(|(existing, _)| existing == key)
+			.map(|(_, value)| value.as_str())
+	}
+
+	#[test]
+	fn decodes_entities_and_applies_key_defaults() {
+		let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- written by hand -->
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="color" attr.type="string"><default>yellow</default></key>
+  <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+  <graph id="G" edgedefault="directed">
+    <node id="n0"><data key="d0">green &amp; &#x41;&#66;</data></node>
+    <node id="n1"/>
+    <edge source="n0" target="n2"><data key="d1">1.5</data></edge>
+    <node id="n2"><data key="d0"><![CDATA[<blue>]]></data></node>
+    <edge source='n1' target="n0"/>
+  </graph>
+</graphml>"#;
+		let (graph, imported) = parse_str(input).unwrap();
+		let names = &imported.names;
+		assert!(imported.directed);
+		assert_eq!(graph.node_count(), 3);
+		assert_eq!(graph.edge_count(), 2);
+		assert_eq!(
+			attribute(graph.node(names["n0"]), "color"),
+			Some("green & AB")
+		);
+		assert_eq!(attribute(graph.node(names["n1"]), "color"), Some("yellow"));
+		assert_eq!(attribute(graph.node(names["n2"]), "color"), Some("<blue>"));
+		let edge = graph.try_edge(EdgeID(names["n0"], names["n2"])).unwrap();
+		assert_eq!(edge, &vec![("weight".to_owned(), "1.5".to_owned())]);
+	}
+
+	#[test]
+	fn reports_direction() {
+		let input = r#"<graphml><graph edgedefault="undirected">
+			<node id="a"/><node id="b"/>
+			<edge source="a" target="b"/>
+			<edge source="b" target="a" directed="true"/>
+		</graph></graphml>"#;
+		let (graph, imported) = parse_str(input).unwrap();
+		let names = &imported.names;
+		assert!(!imported.directed);
+		assert!(graph
+			.try_edge(EdgeID(names["a"], names["b"]))
+			.unwrap()
+			.is_empty());
+		let edge = graph.try_edge(EdgeID(names["b"], names["a"])).unwrap();
+		assert_eq!(attribute(edge, "directed"), Some("true"));
+	}
+
+	#[test]
+	fn reports_error_locations() {
+		let cases = [
+			("<graph/>", 1, 1, "expected `<graphml>`, found `<graph>`"),
+			(
+				"<graphml>\n<graph><node id='a'></graph></graphml>",
+				2,
+				21,
+				"expected `</node>`, found `</graph>`",
+			),
+			(
+				"<graphml><graph>\n  <edge source='a' target='b'/></graph></graphml>",
+				2,
+				3,
+				"edge refers to undeclared node `a`",
+			),
+			(
+				"<graphml><graph><node id='a'><data key='k'/></node></graph></graphml>",
+				1,
+				30,
+				"data refers to undeclared key `k`",
+			),
+			(
+				"<graphml><graph><node/></graph></graphml>",
+				1,
+				17,
+				"`<node>` is missing attribute `id`",
+			),
+			(
+				"<graphml>\n  <x a='&bad;'/></graphml>",
+				2,
+				8,
+				"unknown entity `&bad;`",
+			),
+			("<graphml>", 1, 10, "`<graphml>` is never closed"),
+			(
+				"<graphml><graph edgedefault='up'/></graphml>",
+				1,
+				10,
+				"invalid edgedefault `up`",
+			),
+		];
+		for (input, line, column, message) in cases {
+			let error = parse_str(input).unwrap_err();
+			assert_eq!(
+				(error.line, error.column, error.message.as_str()),
+				(line, column, message)
+			);
+		}
+	}
+}
@@ -0,0 +1,224 @@
+//! Matrix Market coordinate files
+//!
+//! Graphs are exchanged through their adjacency matrices, converted with [`Csr`]
+
+use super::{csr::Csr, Cursor};
+use crate::error::ParseError;
+
+use std::io;
+
+/// Write `matrix` as a real, general coordinate matrix with one-based indices
+pub fn write<W>(matrix: &Csr, mut out: W) -> io::Result<()>
+where
+	W: io::Write,
+{
+	writeln!(out, "%%MatrixMarket matrix coordinate real general")?;
+	writeln!(
+		out,
+		"{} {} {}",
+		matrix.size(),
+		matrix.size(),
+		matrix.entry_count()
+	)?;
+	for (row, column, value) in matrix.entries() {
+		writeln!(out, "{} {} {:?}", row + 1, column + 1, value)?;
+	}
+	Ok(())
+}
+
+/// The most rows a matrix may have for each byte of input
+const ROWS_PER_BYTE: usize = 16;
+
+/// Read a square coordinate matrix
+///
+/// Real, integer and pattern matrices are accepted, with pattern entries given a value of one.
+/// Symmetric and skew-symmetric matrices have the mirror of each off-diagonal entry added
+///
+/// Matrices with more than 16 rows for each byte of input are rejected as too large
+pub fn parse(input: &str) -> Result<Csr, ParseError> {
+	let mut cursor = Cursor::new(input);
+
+	let start = cursor.location();
+	let header = line(&mut cursor).to_ascii_lowercase();
+	let fields = header.split_whitespace().collect::<Vec<_>>();
+	let (field, symmetry) = match fields.as_slice() {
+		["%%matrixmarket", "matrix", "coordinate", field, symmetry] => (*field, *symmetry),
+		["%%matrixmarket", "matrix", format, ..] if *format != "coordinate" => {
+			return Err(start.error(format!("`{}` matrices are not supported", format)));
+		}
+		_ => return Err(start.error("expected a `%%MatrixMarket matrix coordinate` header")),
+	};
+	let has_values = match field {
+		"real" | "integer" => true,
+		"pattern" => false,
+		_ => return Err(start.error(format!("`{}` matrices are not supported", field))),
+	};
+	let mirror = match symmetry {
+		"general" => None,
+		"symmetric" => Some(1.0),
+		"skew-symmetric" => Some(-1.0),
+		_ => return Err(start.error(format!("`{}` matrices are not supported", symmetry))),
+	};
+
+	let (location, sizes) = loop {
+		let location = cursor.location();
+		if cursor.peek().is_none() {
+			return Err(location.error("expected the matrix size"));
+		}
+		let text = line(&mut cursor);
+		if !is_blank(text) {
+			break (location, text);
+		}
+	};
+	let sizes = sizes
+		.split_whitespace()
+		.map(str::parse::<usize>)
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|_| location.error("expected the number of rows, columns and entries"))?;
+	let (size, count) = match sizes.as_slice() {
+		[rows, columns, count] if rows == columns => (*rows, *count),
+		[_, _, _] => return Err(location.error("the matrix of a graph must be square")),
+		_ => return Err(location.error("expected the number of rows, columns and entries")),
+	};
+	if size
+		.checked_mul(size)
+		.is_some_and(|capacity| count > capacity)
+	{
+		return Err(location.error(format!(
+			"{} entries cannot fit in a {} by {} matrix",
+			count, size, size
+		)));
+	}
+	// Every row costs a row pointer whatever the number of entries, so the size is bounded by the
+	// length of the input to keep a short file from claiming a huge amount of memory
+	let mut row_ptr = Vec::new();
+	let fits = size <= input.len().saturating_mul(ROWS_PER_BYTE)
+		&& row_ptr.try_reserve_exact(size + 1).is_ok();
+	if !fits {
+		return Err(location.error(format!("a {} by {} matrix is too large", size, size)));
+	}
+	row_ptr.resize(size + 1, 0);
+
+	let mut entries = Vec::new();
+	let mut found = 0;
+	while cursor.peek().is_some() {
+		let location = cursor.location();
+		let text = line(&mut cursor);
+		if is_blank(text) {
+			continue;
+		}
+		if found == count {
+			return Err(location.error(format!("expected {} entries, found more", count)));
+		}
+		found += 1;
+
+		let fields = text.split_whitespace().collect::<Vec<_>>();
+		let expected = if has_values { 3 } else { 2 };
+		if fields.len() != expected {
+			return Err(location.error(format!(
+				"expected {} fields, found {}",
+				expected,
+				fields.len()
+			)));
+		}
+		let index = |field: &str| match field.parse::<usize>() {
+			Ok(index) if (1..=size).contains(&index) => Ok(index - 1),
+			_ => Err(location.error(format!("`{}` is not an index from 1 to {}", field, size))),
+		};
+		let row = index(fields[0])?;
+		let column = index(fields[1])?;
+		let value = match fields.get(2) {
+			Some(value) => value
+				.parse::<f64>()
+				.map_err(|_| location.error(format!("`{}` is not a number", value)))?,
+			None => 1.0,
+		};
+		entries.push((row, column, value));
+		if let Some(sign) = mirror {
+			if row != column {
+				entries.push((column, row, sign * value));
+			}
+		}
+	}
+	if found < count {
+		return Err(cursor
+			.location()
+			.error(format!("expected {} entries, found {}", count, found)));
+	}
+
+	entries.sort_by_key(|(row, column, _)| (*row, *column));
+	Ok(Csr::from_entries_in(row_ptr, entries))
+}
+
+/// Read the rest of the current line, not including the line break
+fn line<'a>(cursor: &mut Cursor<'a>) -> &'a str {
+	let text = cursor.bump_while(|c| c != '\n');
+	cursor.bump();
+	text.strip_suffix('\r').unwrap_or(text)
+}
+
+/// Whether a line after the header holds no data
+fn is_blank(line: &str) -> bool {
+	let line = line.trim_start();
+	line.is_empty() || line.starts_with('%')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trips_through_csr() {
+		let matrix = Csr::new(vec![0, 2, 2, 3], vec![1, 2, 0], vec![1.0, 2.5, -0.5]);
+		let mut out = Vec::new();
+		write(&matrix, &mut out).unwrap();
+		let text = String::from_utf8(out).unwrap();
+		assert_eq!(
+			text,
+			"%%MatrixMarket matrix coordinate real general\n3 3 3\n1 2 1.0\n1 3 2.5\n3 1 -0.5\n"
+		);
+		assert_eq!(parse(&text).unwrap(), matrix);
+	}
+
+	#[test]
+	fn mirrors_symmetric_entries() {
+		let input =
+			"%%MatrixMarket matrix coordinate pattern symmetric\n% comment\n3 3 2\n2 1\n3 3\n";
+		let matrix = parse(input).unwrap();
+		assert_eq!(
+			matrix.entries().collect::<Vec<_>>(),
+			vec![(0, 1, 1.0), (1, 0, 1.0), (2, 2, 1.0)]
+		);
+	}
+
+	#[test]
+	fn rejects_entry_counts_larger_than_the_matrix() {
+		let error =
+			parse("%%MatrixMarket matrix coordinate real general\n2 2 18446744073709551615\n")
+				.unwrap_err();
+		assert_eq!((error.line, error.column), (2, 1));
+		let error = parse("%%MatrixMarket matrix coordinate real general\n2 2 5\n").unwrap_err();
+		assert_eq!((error.line, error.column), (2, 1));
+	}
+
+	#[test]
+	fn rejects_sizes_the_input_cannot_justify() {
+		let header = "%%MatrixMarket matrix coordinate real general\n";
+		let error = parse(&format!("{}300000000 300000000 0\n", header)).unwrap_err();
+		assert_eq!(
+			(error.line, error.column, error.message.as_str()),
+			(2, 1, "a 300000000 by 300000000 matrix is too large")
+		);
+		let matrix = parse(&format!("{}500 500 0\n", header)).unwrap();
+		assert_eq!(matrix.size(), 500);
+	}
+
+	#[test]
+	fn reports_error_locations() {
+		let error =
+			parse("%%MatrixMarket matrix coordinate real general\n2 2 1\n\n1 3 1.0\n").unwrap_err();
+		assert_eq!((error.line, error.column), (4, 1));
+		let error = parse("%%MatrixMarket matrix array real general\n").unwrap_err();
+		assert_eq!((error.line, error.column), (1, 1));
+	}
+}
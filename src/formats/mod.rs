@@ -0,0 +1,212 @@
+//! Conversions between graphs and external file formats
+//!
+//! The parsers read the whole input before touching the graph, so a graph is left unchanged when
+//! parsing fails
+
+pub mod csr;
+pub mod dot;
+pub mod edge_list;
+pub mod graphml;
+pub mod matrix_market;
+
+use graph_traits::{GraphEdgeAddable, GraphNodeAddable};
+
+use crate::error::ParseError;
+
+use std::collections::HashMap;
+
+/// Attribute key and value pairs, in the order they were given
+pub type Attributes = Vec<(String, String)>;
+
+/// The result of reading a graph from a format which names its nodes and states its direction
+#[derive(Clone, Debug)]
+pub struct Imported<ID> {
+	/// The ID given to each node name
+	pub names: HashMap<String, ID>,
+	/// Whether the input described a directed graph
+	///
+	/// Every edge is added once, from its first node to its second, whatever its direction. When
+	/// reading an undirected graph into a directed graph type, add the reverse edges if they are
+	/// needed
+	pub directed: bool,
+}
+
+/// Set an attribute, replacing any earlier value for the same key
+pub(crate) fn set_attribute(attributes: &mut Attributes, key: String, value: String) {
+	match attributes.iter_mut().find(|(existing, _)| *existing == key) {
+		Some((_, existing)) => *existing = value,
+		None => attributes.push((key, value)),
+	}
+}
+
+/// A line and column within the input
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Location {
+	line: usize,
+	column: usize,
+}
+
+impl Location {
+	pub(crate) fn error<S>(self, message: S) -> ParseError
+	where
+		S: Into<String>,
+	{
+		ParseError {
+			line: self.line,
+			column: self.column,
+			message: message.into(),
+		}
+	}
+}
+
+/// Reads through text one character at a time, keeping track of the current location
+pub(crate) struct Cursor<'a> {
+	rest: &'a str,
+	location: Location,
+}
+
+impl<'a> Cursor<'a> {
+	pub(crate) fn new(input: &'a str) -> Self {
+		Self {
+			rest: input,
+			location: Location { line: 1, column: 1 },
+		}
+	}
+
+	pub(crate) fn location(&self) -> Location { self.location }
+
+	pub(crate) fn peek(&self) -> Option<char> { self.rest.chars().next() }
+
+	pub(crate) fn is_at_line_start(&self) -> bool { self.location.column == 1 }
+
+	/// Read the next character
+	pub(crate) fn bump(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.rest = &self.rest[c.len_utf8()..];
+		if c == '\n' {
+			self.location.line += 1;
+			self.location.column = 1;
+		}
+		else {
+			self.location.column += 1;
+		}
+		Some(c)
+	}
+
+	/// Read `expected` if the remaining text starts with it
+	pub(crate) fn eat(&mut self, expected: &str) -> bool {
+		if self.rest.starts_with(expected) {
+			for _ in expected.chars() {
+				self.bump();
+			}
+			true
+		}
+		else {
+			false
+		}
+	}
+
+	/// Read characters for as long as `predicate` holds, returning them
+	pub(crate) fn bump_while<F>(&mut self, mut predicate: F) -> &'a str
+	where
+		F: FnMut(char) -> bool,
+	{
+		let start = self.rest;
+		while self.peek().is_some_and(&mut predicate) {
+			self.bump();
+		}
+		&start[..start.len() - self.rest.len()]
+	}
+
+	/// Read up to and including `terminator`, returning the text before it
+	pub(crate) fn bump_past(&mut self, terminator: &str) -> Option<&'a str> {
+		let end = self.rest.find(terminator)?;
+		let skipped = &self.rest[..end];
+		for _ in skipped.chars().chain(terminator.chars()) {
+			self.bump();
+		}
+		Some(skipped)
+	}
+}
+
+/// Nodes and edges read from a format which names its nodes and gives them attributes
+#[derive(Default)]
+pub(crate) struct Collected {
+	names: Vec<String>,
+	attributes: Vec<Attributes>,
+	indices: HashMap<String, usize>,
+	edges: Vec<(usize, usize, Attributes)>,
+}
+
+impl Collected {
+	/// The index of the node called `name`, adding it with `defaults` if it has not been seen
+	pub(crate) fn node(&mut self, name: &str, defaults: &Attributes) -> usize {
+		if let Some(&index) = self.indices.get(name) {
+			return index;
+		}
+		let index = self.names.len();
+		self.names.push(name.to_owned());
+		self.attributes.push(defaults.clone());
+		self.indices.insert(name.to_owned(), index);
+		index
+	}
+
+	pub(crate) fn index(&self, name: &str) -> Option<usize> { self.indices.get(name).copied() }
+
+	pub(crate) fn attributes_mut(&mut self, index: usize) -> &mut Attributes {
+		&mut self.attributes[index]
+	}
+
+	pub(crate) fn node_attributes_mut(&mut self) -> impl Iterator<Item = &mut Attributes> {
+		self.attributes.iter_mut()
+	}
+
+	pub(crate) fn add_edge(&mut self, from: usize, to: usize, attributes: Attributes) {
+		self.edges.push((from, to, attributes));
+	}
+
+	/// Add the collected nodes and edges to `graph`, returning the ID given to each node name
+	pub(crate) fn build<G, N, E, FN, FE>(
+		self,
+		graph: &mut G,
+		mut node: FN,
+		mut edge: FE,
+	) -> HashMap<String, G::NodeID>
+	where
+		G: GraphNodeAddable<N> + GraphEdgeAddable<E>,
+		FN: FnMut(&str, &[(String, String)]) -> N,
+		FE: FnMut(&[(String, String)]) -> E,
+	{
+		let ids = self
+			.names
+			.iter()
+			.zip(&self.attributes)
+			.map(|(name, attributes)| graph.add_node(node(name, attributes)))
+			.collect::<Vec<_>>();
+		for (from, to, attributes) in &self.edges {
+			graph.add_edge(ids[*from], ids[*to], edge(attributes));
+		}
+		self.names.into_iter().zip(ids).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cursor_counts_lines_and_characters() {
+		let mut cursor = Cursor::new("é\nab*/cd");
+		assert_eq!(cursor.bump(), Some('é'));
+		assert_eq!((cursor.location().line, cursor.location().column), (1, 2));
+		assert!(cursor.eat("\na"));
+		assert_eq!(cursor.bump_past("*/"), Some("b"));
+		assert_eq!(cursor.bump_while(|c| c != 'd'), "c");
+		let error = cursor.location().error("here");
+		assert_eq!(
+			(error.line, error.column, error.message.as_str()),
+			(2, 6, "here")
+		);
+		assert_eq!(cursor.bump_past("*/"), None);
+	}
+}
@@ -0,0 +1,283 @@
+//! Secondary storage keyed by graph IDs
+//!
+//! Values are stored directly at the slot indices of the IDs rather than hashed, and the full ID is
+//! kept alongside each value so that an ID from a removed node never finds a value stored for a
+//! node which later reused its slot
+//!
+//! The maps work with any ID implementing `NodeIndex` or `EdgeIndex`, which every graph in this
+//! crate provides, and default to the IDs of a `SimpleGraph`
+
+use crate::{
+	simple_graph::{EdgeID, NodeID},
+	traits::{EdgeIndex, NodeIndex},
+};
+
+use std::{iter::Iterator, ops::Index};
+
+/// A map from node IDs to values, stored in a `Vec` indexed by node slot
+#[derive(Clone, Debug)]
+pub struct NodeMap<V, K = NodeID> {
+	slots: Vec<Option<(K, V)>>,
+	len: usize,
+}
+
+impl<V, K> NodeMap<V, K> {
+	pub fn new() -> Self {
+		Self {
+			slots: Vec::new(),
+			len: 0,
+		}
+	}
+
+	/// The number of entries in the map
+	pub fn len(&self) -> usize { self.len }
+
+	/// Whether the map has no entries
+	pub fn is_empty(&self) -> bool { self.len == 0 }
+}
+
+impl<V, K> NodeMap<V, K>
+where
+	K: NodeIndex,
+{
+	/// Get the value stored for a node
+	pub fn get(&self, id: K) -> Option<&V> {
+		match self.slots.get(id.node_index()) {
+			Some(Some((key, value))) if *key == id => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Get the value stored for a node
+	pub fn get_mut(&mut self, id: K) -> Option<&mut V> {
+		match self.slots.get_mut(id.node_index()) {
+			Some(Some((key, value))) if *key == id => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Whether a value is stored for a node
+	pub fn contains_key(&self, id: K) -> bool { self.get(id).is_some() }
+
+	/// Store a value for a node, returning the value previously stored for it
+	///
+	/// Any value left behind by a removed node which used the same slot is dropped
+	pub fn insert(&mut self, id: K, value: V) -> Option<V> {
+		if self.slots.len() <= id.node_index() {
+			self.slots.resize_with(id.node_index() + 1, || None);
+		}
+		match self.slots[id.node_index()].replace((id, value)) {
+			Some((key, previous)) if key == id => Some(previous),
+			Some(_) => None,
+			None => {
+				self.len += 1;
+				None
+			}
+		}
+	}
+
+	/// Remove the value stored for a node
+	pub fn remove(&mut self, id: K) -> Option<V> {
+		let slot = self.slots.get_mut(id.node_index())?;
+		match slot {
+			Some((key, _)) if *key == id => {
+				self.len -= 1;
+				slot.take().map(|(_, value)| value)
+			}
+			_ => None,
+		}
+	}
+
+	/// Remove every entry
+	pub fn clear(&mut self) {
+		self.slots.clear();
+		self.len = 0;
+	}
+
+	/// Iterate over every entry in order of node slot
+	pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+		self.slots
+			.iter()
+			.filter_map(|slot| slot.as_ref().map(|(key, value)| (*key, value)))
+	}
+
+	/// Iterate mutably over every entry in order of node slot
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+		self.slots
+			.iter_mut()
+			.filter_map(|slot| slot.as_mut().map(|(key, value)| (*key, value)))
+	}
+}
+
+impl<V, K> Default for NodeMap<V, K> {
+	fn default() -> Self { Self::new() }
+}
+
+impl<V, K> Index<K> for NodeMap<V, K>
+where
+	K: NodeIndex,
+{
+	type Output = V;
+
+	/// Panics if no value is stored for the node
+	fn index(&self, id: K) -> &V { self.get(id).unwrap() }
+}
+
+/// A map from edge IDs to values, stored in a `Vec<Vec<_>>` indexed by the pair of slots given by
+/// `EdgeIndex`, which for a `SimpleGraph` matches the layout of its edges
+#[derive(Clone, Debug)]
+pub struct EdgeMap<V, K = EdgeID> {
+	rows: Vec<Vec<Option<(K, V)>>>,
+	len: usize,
+}
+
+impl<V, K> EdgeMap<V, K> {
+	pub fn new() -> Self {
+		Self {
+			rows: Vec::new(),
+			len: 0,
+		}
+	}
+
+	/// The number of entries in the map
+	pub fn len(&self) -> usize { self.len }
+
+	/// Whether the map has no entries
+	pub fn is_empty(&self) -> bool { self.len == 0 }
+}
+
+impl<V, K> EdgeMap<V, K>
+where
+	K: EdgeIndex,
+{
+	/// Get the value stored for an edge
+	pub fn get(&self, id: K) -> Option<&V> {
+		let (a, b) = id.edge_index();
+		match self.rows.get(a).and_then(|row| row.get(b)) {
+			Some(Some((key, value))) if *key == id => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Get the value stored for an edge
+	pub fn get_mut(&mut self, id: K) -> Option<&mut V> {
+		let (a, b) = id.edge_index();
+		match self.rows.get_mut(a).and_then(|row| row.get_mut(b)) {
+			Some(Some((key, value))) if *key == id => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Whether a value is stored for an edge
+	pub fn contains_key(&self, id: K) -> bool { self.get(id).is_some() }
+
+	/// Store a value for an edge, returning the value previously stored for it
+	///
+	/// Any value left behind by an edge between removed nodes which used the same slots is dropped
+	pub fn insert(&mut self, id: K, value: V) -> Option<V> {
+		let (a, b) = id.edge_index();
+		if self.rows.len() <= a {
+			self.rows.resize_with(a + 1, Vec::new);
+		}
+		let row = &mut self.rows[a];
+		if row.len() <= b {
+			row.resize_with(b + 1, || None);
+		}
+		match row[b].replace((id, value)) {
+			Some((key, previous)) if key == id => Some(previous),
+			Some(_) => None,
+			None => {
+				self.len += 1;
+				None
+			}
+		}
+	}
+
+	/// Remove the value stored for an edge
+	pub fn remove(&mut self, id: K) -> Option<V> {
+		let (a, b) = id.edge_index();
+		let slot = self.rows.get_mut(a)?.get_mut(b)?;
+		match slot {
+			Some((key, _)) if *key == id => {
+				self.len -= 1;
+				slot.take().map(|(_, value)| value)
+			}
+			_ => None,
+		}
+	}
+
+	/// Remove every entry
+	pub fn clear(&mut self) {
+		self.rows.clear();
+		self.len = 0;
+	}
+
+	/// Iterate over every entry in order of the slots given by `EdgeIndex`
+	pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+		self.rows.iter().flat_map(|row| {
+			row.iter()
+				.filter_map(|slot| slot.as_ref().map(|(key, value)| (*key, value)))
+		})
+	}
+
+	/// Iterate mutably over every entry in order of the slots given by `EdgeIndex`
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+		self.rows.iter_mut().flat_map(|row| {
+			row.iter_mut()
+				.filter_map(|slot| slot.as_mut().map(|(key, value)| (*key, value)))
+		})
+	}
+}
+
+impl<V, K> Default for EdgeMap<V, K> {
+	fn default() -> Self { Self::new() }
+}
+
+impl<V, K> Index<K> for EdgeMap<V, K>
+where
+	K: EdgeIndex,
+{
+	type Output = V;
+
+	/// Panics if no value is stored for the edge
+	fn index(&self, id: K) -> &V { self.get(id).unwrap() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{simple_graph::SimpleGraph, undirected_graph::UndirectedGraph};
+	use graph_traits::{GraphEdgeAddable, GraphNodeAddable, GraphNodeRemovable};
+
+	#[test]
+	fn node_map_rejects_ids_of_removed_nodes() {
+		let mut graph = SimpleGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let mut map = NodeMap::new();
+		map.insert(a, "a");
+		graph.remove_node(a);
+		let b = graph.add_node(());
+
+		assert_eq!(map.get(b), None);
+		assert_eq!(map.insert(b, "b"), None);
+		assert_eq!(map.get(a), None);
+		assert_eq!(map.len(), 1);
+		assert_eq!(map.iter().collect::<Vec<_>>(), vec![(b, &"b")]);
+	}
+
+	#[test]
+	fn edge_map_treats_both_orientations_of_an_undirected_edge_alike() {
+		let mut graph = UndirectedGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let forward = graph.add_edge(a, b, ());
+		let backward = graph.add_edge(b, a, ());
+
+		let mut map = EdgeMap::new();
+		map.insert(forward, 1);
+		assert_eq!(map.insert(backward, 2), Some(1));
+		assert_eq!(map[forward], 2);
+		assert_eq!(map.remove(forward), Some(2));
+		assert!(map.is_empty());
+	}
+}
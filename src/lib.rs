@@ -1 +1,10 @@
+pub mod adjacency_list_graph;
+pub mod algorithms;
+pub mod error;
+pub mod formats;
+pub mod id_map;
+pub mod multi_graph;
 pub mod simple_graph;
+pub mod traits;
+pub mod undirected_graph;
+pub mod union_find;
@@ -0,0 +1,237 @@
+use graph_traits::{
+	GraphBase, GraphEdgeAddable, GraphEdgeEndpoints, GraphEdgeFrom, GraphEdgeIndexable,
+	GraphEdgeMutIndexable, GraphEdgeRemovable, GraphEdgeTo, GraphEdgesFrom, GraphNodeAddable,
+	GraphNodeIndexable, GraphNodeMutIndexable, GraphNodeRemovable,
+};
+
+use crate::traits::{EdgeIndex, GraphNodeIDs, NodeIndex};
+
+use std::iter::Iterator;
+
+/// Opaque struct which represents a node in the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeID(usize);
+
+/// Opaque struct which represents an edge in the graph
+///
+/// Every added edge receives its own ID, even if it runs between the same pair of nodes as another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeID(usize);
+
+impl NodeIndex for NodeID {
+	fn node_index(&self) -> usize { self.0 }
+}
+
+impl EdgeIndex for EdgeID {
+	/// Every edge is laid out in a single row, in the order the edges were added
+	fn edge_index(&self) -> (usize, usize) { (0, self.0) }
+}
+
+/// A directed multigraph implementation, allowing any number of parallel edges between two nodes.
+///
+/// - Nodes are represented with `Vec<Option<N>>`
+///
+/// - Edges are represented with `Vec<Option<(usize, usize, E)>>`, alongside a list of outgoing
+///   edge IDs per node
+///
+/// - All IDs are opaque data structures
+///
+/// Please see the trait implementations for more details
+#[derive(Clone, Debug)]
+pub struct MultiGraph<N, E> {
+	nodes: Vec<Option<N>>,
+	edges: Vec<Option<(usize, usize, E)>>,
+	outgoing: Vec<Vec<usize>>,
+}
+
+impl<N, E> MultiGraph<N, E> {
+	pub fn new() -> Self {
+		Self {
+			nodes: Vec::new(),
+			edges: Vec::new(),
+			outgoing: Vec::new(),
+		}
+	}
+
+	/// Whether a node is in the graph
+	fn has_node(&self, id: usize) -> bool { matches!(self.nodes.get(id), Some(Some(_))) }
+}
+
+impl<N, E> Default for MultiGraph<N, E> {
+	fn default() -> Self { Self::new() }
+}
+
+impl<N, E> GraphBase for MultiGraph<N, E> {
+	type NodeID = NodeID;
+	type EdgeID = EdgeID;
+}
+
+impl<N, E> GraphNodeAddable<N> for MultiGraph<N, E> {
+	/// Identical data added twice will return different node IDs
+	fn add_node(&mut self, data: N) -> Self::NodeID {
+		self.nodes.push(Some(data));
+		self.outgoing.push(Vec::new());
+		NodeID(self.nodes.len() - 1)
+	}
+}
+
+impl<N, E> GraphEdgeAddable<E> for MultiGraph<N, E> {
+	/// Data added from node `A` to `B` will create a new edge alongside any existing edges between
+	/// those nodes
+	///
+	/// Panics if either node is not in the graph
+	fn add_edge(
+		&mut self,
+		NodeID(a): Self::NodeID,
+		NodeID(b): Self::NodeID,
+		data: E,
+	) -> Self::EdgeID {
+		assert!(
+			self.has_node(a) && self.has_node(b),
+			"node is not in the graph"
+		);
+		self.edges.push(Some((a, b, data)));
+		let id = self.edges.len() - 1;
+		self.outgoing[a].push(id);
+		EdgeID(id)
+	}
+}
+
+impl<N, E> GraphNodeRemovable<N> for MultiGraph<N, E> {
+	/// Panics if the node is not in the graph
+	///
+	/// Panics if any edges lead into or out of that node
+	fn remove_node(&mut self, NodeID(id): Self::NodeID) -> N {
+		// Panic if any edges point to or from the removed node
+		assert!(self.outgoing[id].is_empty());
+		for (_, to, _) in self.edges.iter().flatten() {
+			assert!(*to != id);
+		}
+
+		self.nodes[id].take().unwrap()
+	}
+}
+
+impl<N, E> GraphEdgeRemovable<E> for MultiGraph<N, E> {
+	/// Only the given edge is removed, any parallel edges are left in place
+	///
+	/// Panics if the edge is not in the graph
+	fn remove_edge(&mut self, EdgeID(id): Self::EdgeID) -> E {
+		let (from, _, data) = self.edges[id].take().unwrap();
+		let outgoing = &mut self.outgoing[from];
+		let position = outgoing.iter().position(|edge| *edge == id).unwrap();
+		outgoing.remove(position);
+		data
+	}
+}
+
+impl<N, E> GraphNodeIndexable<N> for MultiGraph<N, E> {
+	/// Get the data associated with a node
+	///
+	/// Panics if the node is not in the graph
+	fn node(&self, NodeID(id): Self::NodeID) -> &N { self.nodes[id].as_ref().unwrap() }
+}
+
+impl<N, E> GraphNodeMutIndexable<N> for MultiGraph<N, E> {
+	/// Get the data associated with a node
+	///
+	/// Panics if the node is not in the graph
+	fn node_mut(&mut self, NodeID(id): Self::NodeID) -> &mut N { self.nodes[id].as_mut().unwrap() }
+}
+
+impl<N, E> GraphEdgeIndexable<E> for MultiGraph<N, E> {
+	/// Get the data associated with an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge(&self, EdgeID(id): Self::EdgeID) -> &E { &self.edges[id].as_ref().unwrap().2 }
+}
+
+impl<N, E> GraphEdgeMutIndexable<E> for MultiGraph<N, E> {
+	/// Get the data associated with an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge_mut(&mut self, EdgeID(id): Self::EdgeID) -> &mut E {
+		&mut self.edges[id].as_mut().unwrap().2
+	}
+}
+
+impl<N, E> GraphEdgeTo for MultiGraph<N, E> {
+	/// Find the destination of an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge_to(&self, EdgeID(id): Self::EdgeID) -> Self::NodeID {
+		NodeID(self.edges[id].as_ref().unwrap().1)
+	}
+}
+
+impl<N, E> GraphEdgeFrom for MultiGraph<N, E> {
+	/// Find the source of an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge_from(&self, EdgeID(id): Self::EdgeID) -> Self::NodeID {
+		NodeID(self.edges[id].as_ref().unwrap().0)
+	}
+}
+
+impl<N, E> GraphEdgeEndpoints for MultiGraph<N, E> {}
+
+impl<N, E> GraphEdgesFrom for MultiGraph<N, E> {
+	type EdgesFromOutput = Vec<EdgeID>;
+
+	/// Find all edges from a node, including every parallel edge
+	///
+	/// Return result in a `Vec`
+	fn edges_from(&self, NodeID(id): Self::NodeID) -> Self::EdgesFromOutput {
+		self.outgoing[id].iter().map(|edge| EdgeID(*edge)).collect()
+	}
+}
+
+impl<N, E> GraphNodeIDs for MultiGraph<N, E> {
+	type NodeIDsOutput = Vec<NodeID>;
+
+	/// Find all nodes in the graph
+	///
+	/// Return result in a `Vec`
+	fn node_ids(&self) -> Self::NodeIDsOutput {
+		self.nodes
+			.iter()
+			.enumerate()
+			.filter_map(|(i, node)| node.as_ref().map(|_| NodeID(i)))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keeps_parallel_edges_apart() {
+		let mut graph = MultiGraph::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let first = graph.add_edge(a, b, 1);
+		let second = graph.add_edge(a, b, 2);
+		let back = graph.add_edge(b, a, 3);
+
+		assert_ne!(first, second);
+		assert_eq!(graph.edges_from(a), vec![first, second]);
+		assert_eq!((*graph.edge(first), *graph.edge(second)), (1, 2));
+		assert_eq!(graph.edge_to(second), b);
+
+		assert_eq!(graph.remove_edge(first), 1);
+		assert_eq!(graph.edges_from(a), vec![second]);
+		assert_eq!(graph.edges_from(b), vec![back]);
+		assert_eq!(*graph.edge(second), 2);
+	}
+
+	#[test]
+	#[should_panic(expected = "node is not in the graph")]
+	fn rejects_edges_to_removed_nodes() {
+		let mut graph = MultiGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		graph.remove_node(b);
+		graph.add_edge(a, b, ());
+	}
+}
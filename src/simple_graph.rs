@@ -4,38 +4,453 @@ use graph_traits::{
 	GraphNodeIndexable, GraphNodeMutIndexable, GraphNodeRemovable,
 };
 
-use std::{cmp::max, iter::Iterator, mem::replace};
+use crate::{
+	error::GraphError,
+	traits::{EdgeIndex, GraphEdgesTo, GraphNodeIDs, NodeIndex},
+};
+
+use std::{cmp::max, iter::Iterator};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+mod serialization;
 
 /// Opaque struct which represents a node in the graph
-#[derive(Debug, Clone, Copy)]
-pub struct NodeID(usize);
+///
+/// Carries the generation of the slot it was issued for, so the ID of a removed node never refers
+/// to a node which later reuses its slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NodeID {
+	pub(crate) index: usize,
+	generation: usize,
+}
 
 /// Opaque struct which represents an edge in the graph
-#[derive(Debug, Clone, Copy)]
-pub struct EdgeID((usize, usize));
+///
+/// Made up of the IDs of both endpoints, so it is rejected once either endpoint is removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EdgeID(pub(crate) NodeID, pub(crate) NodeID);
+
+impl NodeIndex for NodeID {
+	fn node_index(&self) -> usize { self.index }
+}
+
+impl EdgeIndex for EdgeID {
+	fn edge_index(&self) -> (usize, usize) { (self.0.index, self.1.index) }
+}
 
 /// A simple directed graph implementation.
 ///
-/// - Nodes are represented with `Vec<N>`
+/// - Nodes are represented with `Vec<Option<N>>`, alongside a generation counter per slot
 ///
 /// - Edges are represented with `Vec<Vec<Option<E>>>`
 ///
 /// - All IDs are opaque data structures
 ///
+/// Slots of removed nodes are reused by later calls to `add_node`
+///
+/// With the `serde` feature enabled, graphs can be serialized without invalidating any IDs
+///
+/// Finding the edges into a node scans every row of the edge matrix unless the optional reverse
+/// index is enabled, which keeps a list of source nodes per node
+///
 /// Please see the trait implementations for more details
 #[derive(Clone, Debug)]
 pub struct SimpleGraph<N, E> {
 	nodes: Vec<Option<N>>,
+	generations: Vec<usize>,
+	free_slots: Vec<usize>,
 	edges: Vec<Vec<Option<E>>>,
+	reverse_index: Option<Vec<Vec<usize>>>,
+	edge_count: usize,
+}
+
+/// Build the current ID of the node in a slot
+fn node_id(generations: &[usize], index: usize) -> NodeID {
+	NodeID {
+		index,
+		generation: generations[index],
+	}
 }
 
 impl<N, E> SimpleGraph<N, E> {
 	pub fn new() -> Self {
 		Self {
 			nodes: Vec::new(),
+			generations: Vec::new(),
+			free_slots: Vec::new(),
 			edges: Vec::new(),
+			reverse_index: None,
+			edge_count: 0,
+		}
+	}
+
+	/// Create a graph which maintains a reverse index, making `edges_to` proportional to the
+	/// number of edges into the node rather than the number of nodes
+	pub fn with_reverse_index() -> Self {
+		Self {
+			reverse_index: Some(Vec::new()),
+			..Self::new()
+		}
+	}
+
+	/// Build the reverse index from the current edges and maintain it from now on
+	pub fn enable_reverse_index(&mut self) {
+		let mut reverse_index = vec![Vec::new(); self.nodes.len()];
+		for (from, row) in self.edges.iter().enumerate() {
+			for (to, slot) in row.iter().enumerate() {
+				if slot.is_some() {
+					reverse_index[to].push(from);
+				}
+			}
+		}
+		self.reverse_index = Some(reverse_index);
+	}
+
+	/// Drop the reverse index, falling back to scanning the edge matrix in `edges_to`
+	pub fn disable_reverse_index(&mut self) { self.reverse_index = None; }
+
+	pub fn has_reverse_index(&self) -> bool { self.reverse_index.is_some() }
+
+	/// The raw edge matrix, indexed by the slots of the source and destination nodes
+	///
+	/// Rows and columns may be shorter than the number of slots
+	pub(crate) fn edge_matrix(&self) -> &[Vec<Option<E>>] { &self.edges }
+
+	/// The number of nodes in the graph
+	pub fn node_count(&self) -> usize { self.nodes.len() - self.free_slots.len() }
+
+	/// The number of edges in the graph
+	pub fn edge_count(&self) -> usize { self.edge_count }
+
+	/// Whether the graph has no nodes
+	pub fn is_empty(&self) -> bool { self.node_count() == 0 }
+
+	fn contains_node(&self, NodeID { index, generation }: NodeID) -> bool {
+		matches!(self.nodes.get(index), Some(Some(_))) && self.generations[index] == generation
+	}
+
+	fn node_id(&self, index: usize) -> NodeID { node_id(&self.generations, index) }
+
+	fn edge_slot(&self, EdgeID(a, b): EdgeID) -> Option<&Option<E>> {
+		if !self.contains_node(a) || !self.contains_node(b) {
+			return None;
+		}
+		self.edges.get(a.index).and_then(|row| row.get(b.index))
+	}
+
+	fn edge_slot_mut(&mut self, EdgeID(a, b): EdgeID) -> Option<&mut Option<E>> {
+		if !self.contains_node(a) || !self.contains_node(b) {
+			return None;
+		}
+		self.edges
+			.get_mut(a.index)
+			.and_then(|row| row.get_mut(b.index))
+	}
+
+	/// Get the data associated with a node
+	///
+	/// Fails with `GraphError::NodeNotFound` if the node is not in the graph
+	pub fn try_node(&self, id: NodeID) -> Result<&N, GraphError> {
+		if !self.contains_node(id) {
+			return Err(GraphError::NodeNotFound);
+		}
+		self.nodes[id.index]
+			.as_ref()
+			.ok_or(GraphError::NodeNotFound)
+	}
+
+	/// Get the data associated with a node
+	///
+	/// Fails with `GraphError::NodeNotFound` if the node is not in the graph
+	pub fn try_node_mut(&mut self, id: NodeID) -> Result<&mut N, GraphError> {
+		if !self.contains_node(id) {
+			return Err(GraphError::NodeNotFound);
+		}
+		self.nodes[id.index]
+			.as_mut()
+			.ok_or(GraphError::NodeNotFound)
+	}
+
+	/// Get the data associated with an edge
+	///
+	/// Fails with `GraphError::EdgeNotFound` if the edge is not in the graph
+	pub fn try_edge(&self, id: EdgeID) -> Result<&E, GraphError> {
+		self.edge_slot(id)
+			.and_then(Option::as_ref)
+			.ok_or(GraphError::EdgeNotFound)
+	}
+
+	/// Get the data associated with an edge
+	///
+	/// Fails with `GraphError::EdgeNotFound` if the edge is not in the graph
+	pub fn try_edge_mut(&mut self, id: EdgeID) -> Result<&mut E, GraphError> {
+		self.edge_slot_mut(id)
+			.and_then(Option::as_mut)
+			.ok_or(GraphError::EdgeNotFound)
+	}
+
+	/// Data added from node `A` to `B` will overwrite the data that was previously between those edges
+	///
+	/// Fails with `GraphError::NodeNotFound` if either node is not in the graph
+	pub fn try_add_edge(&mut self, a: NodeID, b: NodeID, data: E) -> Result<EdgeID, GraphError>
+	where
+		E: Clone,
+	{
+		if !self.contains_node(a) || !self.contains_node(b) {
+			return Err(GraphError::NodeNotFound);
+		}
+
+		let max_id = max(a.index, b.index);
+
+		// Make sure that the vectors are large enough to contain the node IDs
+		if self.edges.len() <= max_id {
+			self.edges.resize(max_id + 1, Vec::new());
+		}
+		if self.edges[a.index].len() <= b.index {
+			self.edges[a.index].resize(b.index + 1, None);
+		}
+
+		let previous = self.edges[a.index][b.index].replace(data);
+		if previous.is_none() {
+			self.edge_count += 1;
+			if let Some(reverse_index) = &mut self.reverse_index {
+				if reverse_index.len() <= b.index {
+					reverse_index.resize(b.index + 1, Vec::new());
+				}
+				reverse_index[b.index].push(a.index);
+			}
+		}
+		Ok(EdgeID(a, b))
+	}
+
+	/// The slot of the removed node will be reused by a later call to `add_node`, but `id` will
+	/// never refer to the new node
+	///
+	/// Fails with `GraphError::NodeNotFound` if the node is not in the graph
+	///
+	/// Fails with `GraphError::NodeHasEdges` if any edges lead into or out of that node
+	pub fn try_remove_node(&mut self, id: NodeID) -> Result<N, GraphError> {
+		if !self.contains_node(id) {
+			return Err(GraphError::NodeNotFound);
+		}
+
+		// Refuse to remove the node if any edges point to or from it
+		let has_edges_from = self
+			.edges
+			.get(id.index)
+			.is_some_and(|row| row.iter().any(Option::is_some));
+		let has_edges_to = self
+			.edges
+			.iter()
+			.any(|row| matches!(row.get(id.index), Some(Some(_))));
+		if has_edges_from || has_edges_to {
+			return Err(GraphError::NodeHasEdges);
+		}
+
+		self.retire_node(id)
+	}
+
+	/// Remove a node together with every edge leading into or out of it
+	///
+	/// Returns the data of the node along with the ID and data of each removed edge
+	///
+	/// Fails with `GraphError::NodeNotFound` if the node is not in the graph
+	pub fn try_remove_node_with_edges(
+		&mut self,
+		id: NodeID,
+	) -> Result<(N, Vec<(EdgeID, E)>), GraphError> {
+		if !self.contains_node(id) {
+			return Err(GraphError::NodeNotFound);
+		}
+
+		let generations = &self.generations;
+		let mut removed = Vec::new();
+
+		// Edges out of the node, including any self loop
+		if let Some(row) = self.edges.get_mut(id.index) {
+			for (i, slot) in row.iter_mut().enumerate() {
+				if let Some(data) = slot.take() {
+					removed.push((EdgeID(id, node_id(generations, i)), data));
+				}
+			}
+		}
+
+		// Edges into the node
+		for (i, row) in self.edges.iter_mut().enumerate() {
+			if let Some(data) = row.get_mut(id.index).and_then(Option::take) {
+				removed.push((EdgeID(node_id(generations, i), id), data));
+			}
+		}
+
+		if self.reverse_index.is_some() {
+			for (EdgeID(a, b), _) in &removed {
+				self.unindex_edge(a.index, b.index);
+			}
+		}
+
+		self.edge_count -= removed.len();
+		let data = self.retire_node(id)?;
+		Ok((data, removed))
+	}
+
+	/// Remove a node together with every edge leading into or out of it
+	///
+	/// Returns the data of the node along with the ID and data of each removed edge
+	///
+	/// Panics if the node is not in the graph
+	pub fn remove_node_with_edges(&mut self, id: NodeID) -> (N, Vec<(EdgeID, E)>) {
+		self.try_remove_node_with_edges(id).unwrap()
+	}
+
+	/// Swap out the node and retire its ID so that its slot can be reused
+	fn retire_node(&mut self, id: NodeID) -> Result<N, GraphError> {
+		let data = self.nodes[id.index]
+			.take()
+			.ok_or(GraphError::NodeNotFound)?;
+		self.generations[id.index] += 1;
+		self.free_slots.push(id.index);
+		Ok(data)
+	}
+
+	/// Fails with `GraphError::EdgeNotFound` if the edge is not in the graph
+	pub fn try_remove_edge(&mut self, id: EdgeID) -> Result<E, GraphError> {
+		let data = self
+			.edge_slot_mut(id)
+			.and_then(Option::take)
+			.ok_or(GraphError::EdgeNotFound)?;
+		let EdgeID(a, b) = id;
+		self.unindex_edge(a.index, b.index);
+		self.edge_count -= 1;
+		Ok(data)
+	}
+
+	/// Remove an edge from the reverse index, if it is enabled
+	fn unindex_edge(&mut self, a: usize, b: usize) {
+		if let Some(sources) = self
+			.reverse_index
+			.as_mut()
+			.and_then(|reverse_index| reverse_index.get_mut(b))
+		{
+			if let Some(position) = sources.iter().position(|source| *source == a) {
+				sources.swap_remove(position);
+			}
 		}
 	}
+
+	/// Find all edges from a node
+	///
+	/// Fails with `GraphError::NodeNotFound` if the node is not in the graph
+	pub fn try_edges_from(&self, id: NodeID) -> Result<Vec<EdgeID>, GraphError> {
+		if !self.contains_node(id) {
+			return Err(GraphError::NodeNotFound);
+		}
+
+		let edges = match self.edges.get(id.index) {
+			Some(row) => row
+				.iter()
+				.enumerate()
+				.filter_map(|(i, dest)| dest.as_ref().map(|_| EdgeID(id, self.node_id(i))))
+				.collect(),
+			None => Vec::new(),
+		};
+		Ok(edges)
+	}
+
+	/// Find all edges into a node
+	///
+	/// Scans every row of the edge matrix unless the reverse index is enabled
+	///
+	/// Fails with `GraphError::NodeNotFound` if the node is not in the graph
+	pub fn try_edges_to(&self, id: NodeID) -> Result<Vec<EdgeID>, GraphError> {
+		if !self.contains_node(id) {
+			return Err(GraphError::NodeNotFound);
+		}
+
+		let edges = match &self.reverse_index {
+			Some(reverse_index) => match reverse_index.get(id.index) {
+				Some(sources) => sources
+					.iter()
+					.map(|source| EdgeID(self.node_id(*source), id))
+					.collect(),
+				None => Vec::new(),
+			},
+			None => self
+				.edges
+				.iter()
+				.enumerate()
+				.filter(|(_, row)| matches!(row.get(id.index), Some(Some(_))))
+				.map(|(i, _)| EdgeID(self.node_id(i), id))
+				.collect(),
+		};
+		Ok(edges)
+	}
+
+	/// Iterate over the IDs of all nodes in the graph, skipping removed slots
+	pub fn node_ids(&self) -> impl Iterator<Item = NodeID> + '_ { self.nodes().map(|(id, _)| id) }
+
+	/// Iterate over the IDs of all edges in the graph
+	pub fn edge_ids(&self) -> impl Iterator<Item = EdgeID> + '_ { self.edges().map(|(id, ..)| id) }
+
+	/// Iterate over all nodes in the graph along with their data, skipping removed slots
+	pub fn nodes(&self) -> impl Iterator<Item = (NodeID, &N)> {
+		self.nodes
+			.iter()
+			.zip(&self.generations)
+			.enumerate()
+			.filter_map(|(index, (slot, generation))| {
+				let generation = *generation;
+				slot.as_ref()
+					.map(|data| (NodeID { index, generation }, data))
+			})
+	}
+
+	/// Iterate mutably over all nodes in the graph along with their data, skipping removed slots
+	pub fn nodes_mut(&mut self) -> impl Iterator<Item = (NodeID, &mut N)> {
+		self.nodes
+			.iter_mut()
+			.zip(&self.generations)
+			.enumerate()
+			.filter_map(|(index, (slot, generation))| {
+				let generation = *generation;
+				slot.as_mut()
+					.map(|data| (NodeID { index, generation }, data))
+			})
+	}
+
+	/// Iterate over all edges in the graph along with their source, destination and data
+	pub fn edges(&self) -> impl Iterator<Item = (EdgeID, NodeID, NodeID, &E)> {
+		let generations = &self.generations;
+		self.edges.iter().enumerate().flat_map(move |(a, row)| {
+			row.iter().enumerate().filter_map(move |(b, slot)| {
+				slot.as_ref().map(|data| {
+					let (a, b) = (node_id(generations, a), node_id(generations, b));
+					(EdgeID(a, b), a, b, data)
+				})
+			})
+		})
+	}
+
+	/// Iterate mutably over all edges in the graph along with their source, destination and data
+	pub fn edges_mut(&mut self) -> impl Iterator<Item = (EdgeID, NodeID, NodeID, &mut E)> {
+		let generations = &self.generations;
+		self.edges.iter_mut().enumerate().flat_map(move |(a, row)| {
+			row.iter_mut().enumerate().filter_map(move |(b, slot)| {
+				slot.as_mut().map(|data| {
+					let (a, b) = (node_id(generations, a), node_id(generations, b));
+					(EdgeID(a, b), a, b, data)
+				})
+			})
+		})
+	}
+}
+
+impl<N, E> Default for SimpleGraph<N, E> {
+	fn default() -> Self { Self::new() }
 }
 
 impl<N, E> GraphBase for SimpleGraph<N, E> {
@@ -45,9 +460,20 @@ impl<N, E> GraphBase for SimpleGraph<N, E> {
 
 impl<N, E> GraphNodeAddable<N> for SimpleGraph<N, E> {
 	/// Identical data added twice will return different node IDs
+	///
+	/// Reuses the slot of a previously removed node if one is available
 	fn add_node(&mut self, data: N) -> Self::NodeID {
-		self.nodes.push(Some(data));
-		NodeID(self.nodes.len() - 1)
+		match self.free_slots.pop() {
+			Some(index) => {
+				self.nodes[index] = Some(data);
+				self.node_id(index)
+			}
+			None => {
+				self.nodes.push(Some(data));
+				self.generations.push(0);
+				self.node_id(self.nodes.len() - 1)
+			}
+		}
 	}
 }
 
@@ -56,89 +482,61 @@ where
 	E: Clone,
 {
 	/// Data added from node `A` to `B` will overwrite the data that was previously between those edges
+	///
+	/// Panics if either node is not in the graph
 	fn add_edge(&mut self, a: Self::NodeID, b: Self::NodeID, data: E) -> Self::EdgeID {
-		let (a, b) = (a.0, b.0);
-
-		let max_id = max(a, b);
-
-		// Make sure that the vectors are large enough to contain the node IDs
-		if self.edges.len() <= max_id {
-			self.edges.resize(max_id + 1, Vec::new());
-		}
-		if self.edges[a].len() <= b {
-			self.edges[a].resize(b + 1, None);
-		}
-
-		self.edges[a][b] = Some(data);
-		EdgeID((a, b))
+		self.try_add_edge(a, b, data).unwrap()
 	}
 }
 
 impl<N, E> GraphNodeRemovable<N> for SimpleGraph<N, E> {
 	/// Panics if the node is not in the graph
 	///
-	/// Panics if any edges lead into or out of that edge
-	fn remove_node(&mut self, NodeID(id): Self::NodeID) -> N {
-		// Panic if any edges point to or from the removed node
-		for to_node in &self.edges[id] {
-			assert!(to_node.is_none());
-		}
-		for from_node in &self.edges {
-			assert!(from_node[id].is_none());
-		}
-
-		// Swap out the node
-		replace(&mut self.nodes[id], None).unwrap()
-	}
+	/// Panics if any edges lead into or out of that node
+	fn remove_node(&mut self, id: Self::NodeID) -> N { self.try_remove_node(id).unwrap() }
 }
 
 impl<N, E> GraphEdgeRemovable<E> for SimpleGraph<N, E> {
-	/// Panics if the node is not in the graph
-	fn remove_edge(&mut self, EdgeID((id_a, id_b)): Self::EdgeID) -> E {
-		replace(&mut self.edges[id_a][id_b], None).unwrap()
-	}
+	/// Panics if the edge is not in the graph
+	fn remove_edge(&mut self, id: Self::EdgeID) -> E { self.try_remove_edge(id).unwrap() }
 }
 
 impl<N, E> GraphNodeIndexable<N> for SimpleGraph<N, E> {
 	/// Get the data associated with a node
 	///
 	/// Panics if the node is not in the graph
-	fn node(&self, NodeID(id): Self::NodeID) -> &N { self.nodes[id].as_ref().unwrap() }
+	fn node(&self, id: Self::NodeID) -> &N { self.try_node(id).unwrap() }
 }
 
 impl<N, E> GraphNodeMutIndexable<N> for SimpleGraph<N, E> {
 	/// Get the data associated with a node
 	///
 	/// Panics if the node is not in the graph
-	fn node_mut(&mut self, NodeID(id): Self::NodeID) -> &mut N { self.nodes[id].as_mut().unwrap() }
+	fn node_mut(&mut self, id: Self::NodeID) -> &mut N { self.try_node_mut(id).unwrap() }
 }
 
 impl<N, E> GraphEdgeIndexable<E> for SimpleGraph<N, E> {
 	/// Get the data associated with an edge
 	///
 	/// Panics if the edge is not in the graph
-	fn edge(&self, EdgeID((id_a, id_b)): Self::EdgeID) -> &E {
-		self.edges[id_a][id_b].as_ref().unwrap()
-	}
+	fn edge(&self, id: Self::EdgeID) -> &E { self.try_edge(id).unwrap() }
 }
 
 impl<N, E> GraphEdgeMutIndexable<E> for SimpleGraph<N, E> {
 	/// Get the data associated with an edge
 	///
 	/// Panics if the edge is not in the graph
-	fn edge_mut(&mut self, EdgeID((id_a, id_b)): Self::EdgeID) -> &mut E {
-		self.edges[id_a][id_b].as_mut().unwrap()
-	}
+	fn edge_mut(&mut self, id: Self::EdgeID) -> &mut E { self.try_edge_mut(id).unwrap() }
 }
 
 impl<N, E> GraphEdgeTo for SimpleGraph<N, E> {
 	/// Find the destination of an edge
-	fn edge_to(&self, EdgeID((_, id_b)): Self::EdgeID) -> Self::NodeID { NodeID(id_b) }
+	fn edge_to(&self, EdgeID(_, b): Self::EdgeID) -> Self::NodeID { b }
 }
 
 impl<N, E> GraphEdgeFrom for SimpleGraph<N, E> {
 	/// Find the source of an edge
-	fn edge_from(&self, EdgeID((id_a, _)): Self::EdgeID) -> Self::NodeID { NodeID(id_a) }
+	fn edge_from(&self, EdgeID(a, _): Self::EdgeID) -> Self::NodeID { a }
 }
 
 impl<N, E> GraphEdgeEndpoints for SimpleGraph<N, E> {}
@@ -149,14 +547,222 @@ impl<N, E> GraphEdgesFrom for SimpleGraph<N, E> {
 	/// Find all edges from a node
 	///
 	/// Return result in a `Vec`
-	fn edges_from(&self, NodeID(id): Self::NodeID) -> Self::EdgesFromOutput {
-		self.edges[id]
-			.iter()
-			.enumerate()
-			.filter_map(|(i, dest)| match dest {
-				Some(_) => Some(EdgeID((id, i))),
-				None => None,
-			})
-			.collect()
+	///
+	/// Panics if the node is not in the graph
+	fn edges_from(&self, id: Self::NodeID) -> Self::EdgesFromOutput {
+		self.try_edges_from(id).unwrap()
+	}
+}
+
+impl<N, E> GraphEdgesTo for SimpleGraph<N, E> {
+	type EdgesToOutput = Vec<EdgeID>;
+
+	/// Find all edges into a node
+	///
+	/// Return result in a `Vec`
+	///
+	/// Panics if the node is not in the graph
+	fn edges_to(&self, id: Self::NodeID) -> Self::EdgesToOutput { self.try_edges_to(id).unwrap() }
+}
+
+impl<N, E> GraphNodeIDs for SimpleGraph<N, E> {
+	type NodeIDsOutput = Vec<NodeID>;
+
+	/// Find all nodes in the graph
+	///
+	/// Return result in a `Vec`
+	fn node_ids(&self) -> Self::NodeIDsOutput { SimpleGraph::node_ids(self).collect() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rejects_stale_ids_after_slot_reuse() {
+		let mut graph = SimpleGraph::<&str, ()>::new();
+		let old = graph.add_node("old");
+		let other = graph.add_node("other");
+		assert_eq!(graph.remove_node(old), "old");
+		let new = graph.add_node("new");
+
+		assert_eq!(new.index, old.index);
+		assert_ne!(new, old);
+		assert_eq!(*graph.node(new), "new");
+		assert_eq!(graph.try_node(old), Err(GraphError::NodeNotFound));
+		assert_eq!(graph.try_node_mut(old), Err(GraphError::NodeNotFound));
+		assert_eq!(
+			graph.try_add_edge(old, other, ()),
+			Err(GraphError::NodeNotFound)
+		);
+		assert_eq!(graph.try_edges_from(old), Err(GraphError::NodeNotFound));
+		assert_eq!(graph.try_remove_node(old), Err(GraphError::NodeNotFound));
+		assert_eq!(graph.node_ids().collect::<Vec<_>>(), vec![new, other]);
+	}
+
+	#[test]
+	fn rejects_edges_with_a_stale_endpoint() {
+		let mut graph = SimpleGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let edge = graph.add_edge(a, b, ());
+		graph.remove_node_with_edges(b);
+		let c = graph.add_node(());
+		graph.add_edge(a, c, ());
+
+		assert_eq!(graph.try_edge(edge), Err(GraphError::EdgeNotFound));
+		assert_eq!(graph.try_remove_edge(edge), Err(GraphError::EdgeNotFound));
+	}
+
+	#[test]
+	fn reuses_freed_slots_most_recent_first() {
+		let mut graph = SimpleGraph::<(), ()>::new();
+		let nodes = (0..4).map(|_| graph.add_node(())).collect::<Vec<_>>();
+		graph.remove_node(nodes[3]);
+		graph.remove_node(nodes[1]);
+		assert_eq!(graph.node_count(), 2);
+
+		assert_eq!(graph.add_node(()).index, 1);
+		assert_eq!(graph.add_node(()).index, 3);
+		assert_eq!(graph.add_node(()).index, 4);
+		assert_eq!(graph.node_count(), 5);
+	}
+
+	/// Check that every node has the same edges into it with and without the reverse index
+	fn assert_reverse_index_matches_scan(graph: &SimpleGraph<(), i32>) {
+		assert!(graph.has_reverse_index());
+		let mut scanned = graph.clone();
+		scanned.disable_reverse_index();
+		for node in graph.node_ids() {
+			let mut indexed = graph.edges_to(node);
+			let mut expected = scanned.edges_to(node);
+			indexed.sort();
+			expected.sort();
+			assert_eq!(indexed, expected);
+		}
+	}
+
+	#[test]
+	fn reverse_index_ignores_overwritten_edges() {
+		let mut graph = SimpleGraph::with_reverse_index();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let edge = graph.add_edge(a, b, 1);
+		graph.add_edge(a, b, 2);
+		assert_eq!(graph.edges_to(b), vec![edge]);
+		assert_reverse_index_matches_scan(&graph);
+	}
+
+	#[test]
+	fn reverse_index_follows_removed_edges() {
+		let mut graph = SimpleGraph::with_reverse_index();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let c = graph.add_node(());
+		let from_a = graph.add_edge(a, c, 1);
+		let from_b = graph.add_edge(b, c, 2);
+		graph.remove_edge(from_a);
+		assert_eq!(graph.edges_to(c), vec![from_b]);
+		assert_reverse_index_matches_scan(&graph);
+	}
+
+	#[test]
+	fn reverse_index_follows_removed_nodes() {
+		let mut graph = SimpleGraph::with_reverse_index();
+		let nodes = (0..3).map(|_| graph.add_node(())).collect::<Vec<_>>();
+		for (from, to) in [(0, 1), (1, 1), (1, 2), (2, 0), (0, 2)] {
+			graph.add_edge(nodes[from], nodes[to], 0);
+		}
+		graph.remove_node_with_edges(nodes[1]);
+		assert_eq!(graph.edges_to(nodes[2]), vec![EdgeID(nodes[0], nodes[2])]);
+		assert_reverse_index_matches_scan(&graph);
+
+		// The reused slot starts out with no edges into it
+		let new = graph.add_node(());
+		assert!(graph.edges_to(new).is_empty());
+		graph.add_edge(nodes[2], new, 0);
+		assert_reverse_index_matches_scan(&graph);
+	}
+
+	#[test]
+	fn reverse_index_can_be_enabled_and_disabled() {
+		let mut graph = SimpleGraph::new();
+		let nodes = (0..3).map(|_| graph.add_node(())).collect::<Vec<_>>();
+		for (from, to) in [(0, 2), (1, 2), (2, 2), (2, 0)] {
+			graph.add_edge(nodes[from], nodes[to], 0);
+		}
+		assert!(!graph.has_reverse_index());
+
+		graph.enable_reverse_index();
+		assert_reverse_index_matches_scan(&graph);
+		graph.add_edge(nodes[1], nodes[0], 0);
+		assert_reverse_index_matches_scan(&graph);
+
+		graph.disable_reverse_index();
+		assert!(!graph.has_reverse_index());
+		let mut edges = graph.edges_to(nodes[0]);
+		edges.sort();
+		assert_eq!(
+			edges,
+			vec![EdgeID(nodes[1], nodes[0]), EdgeID(nodes[2], nodes[0])]
+		);
+	}
+
+	#[test]
+	fn counts_nodes_and_edges() {
+		let mut graph = SimpleGraph::<(), i32>::new();
+		assert!(graph.is_empty());
+		let nodes = (0..3).map(|_| graph.add_node(())).collect::<Vec<_>>();
+		for (from, to) in [(0, 1), (1, 1), (1, 2), (2, 0), (0, 2)] {
+			graph.add_edge(nodes[from], nodes[to], 0);
+		}
+		assert_eq!((graph.node_count(), graph.edge_count()), (3, 5));
+
+		graph.add_edge(nodes[0], nodes[1], 1);
+		assert_eq!(graph.edge_count(), 5);
+
+		graph.remove_edge(EdgeID(nodes[2], nodes[0]));
+		assert_eq!(graph.edge_count(), 4);
+
+		// The self loop and the edges into and out of the node are all removed
+		let (_, removed) = graph.remove_node_with_edges(nodes[1]);
+		assert_eq!(removed.len(), 3);
+		assert_eq!((graph.node_count(), graph.edge_count()), (2, 1));
+		assert_eq!(graph.edge_ids().count(), 1);
+	}
+
+	#[test]
+	fn reports_each_kind_of_error() {
+		let mut graph = SimpleGraph::<(), i32>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let gone = graph.add_node(());
+		graph.remove_node(gone);
+
+		assert_eq!(graph.try_node(gone), Err(GraphError::NodeNotFound));
+		assert_eq!(graph.try_edges_to(gone), Err(GraphError::NodeNotFound));
+		assert_eq!(
+			graph.try_remove_node_with_edges(gone).unwrap_err(),
+			GraphError::NodeNotFound
+		);
+
+		let missing = EdgeID(a, b);
+		assert_eq!(graph.try_edge(missing), Err(GraphError::EdgeNotFound));
+		assert_eq!(graph.try_edge_mut(missing), Err(GraphError::EdgeNotFound));
+		assert_eq!(
+			graph.try_remove_edge(missing),
+			Err(GraphError::EdgeNotFound)
+		);
+
+		let edge = graph.try_add_edge(a, b, 1).unwrap();
+		assert_eq!(graph.try_remove_node(a), Err(GraphError::NodeHasEdges));
+		assert_eq!(graph.try_remove_node(b), Err(GraphError::NodeHasEdges));
+		*graph.try_edge_mut(edge).unwrap() = 2;
+		assert_eq!(graph.try_remove_edge(edge), Ok(2));
+		assert_eq!(graph.try_remove_node(a), Ok(()));
+		assert_eq!(
+			GraphError::NodeHasEdges.to_string(),
+			"node still has edges leading into or out of it"
+		);
 	}
 }
@@ -0,0 +1,173 @@
+//! Serde support for `SimpleGraph`
+//!
+//! Graphs are encoded as a list of node slots and a list of edges rather than the edge matrix.
+//! Every slot is kept along with its generation, including the slots of removed nodes, so IDs
+//! held elsewhere remain valid after a round trip. The slots of removed nodes are listed in the
+//! order `add_node` will reuse them
+
+use super::SimpleGraph;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+struct Encoded<N, E> {
+	/// The generation and data of every node slot, with `None` for removed nodes
+	nodes: Vec<(usize, Option<N>)>,
+	/// The source slot, destination slot and data of every edge
+	edges: Vec<(usize, usize, E)>,
+	/// The slots of removed nodes, with the next to be reused last
+	free_slots: Vec<usize>,
+	reverse_index: bool,
+}
+
+impl<N, E> Serialize for SimpleGraph<N, E>
+where
+	N: Serialize,
+	E: Serialize,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let encoded = Encoded {
+			nodes: self
+				.nodes
+				.iter()
+				.zip(&self.generations)
+				.map(|(node, generation)| (*generation, node.as_ref()))
+				.collect(),
+			edges: self
+				.edges()
+				.map(|(_, from, to, data)| (from.index, to.index, data))
+				.collect(),
+			free_slots: self.free_slots.clone(),
+			reverse_index: self.has_reverse_index(),
+		};
+		encoded.serialize(serializer)
+	}
+}
+
+impl<'de, N, E> Deserialize<'de> for SimpleGraph<N, E>
+where
+	N: Deserialize<'de>,
+	E: Deserialize<'de>,
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let encoded = Encoded::<N, E>::deserialize(deserializer)?;
+
+		let mut graph = SimpleGraph::new();
+		for (generation, node) in encoded.nodes {
+			graph.nodes.push(node);
+			graph.generations.push(generation);
+		}
+
+		// Every removed slot must be listed exactly once for `add_node` to reuse
+		let mut listed = encoded.free_slots.clone();
+		listed.sort_unstable();
+		let removed = (0..graph.nodes.len()).filter(|index| graph.nodes[*index].is_none());
+		if !listed.into_iter().eq(removed) {
+			return Err(D::Error::custom(
+				"free slots do not match the removed nodes",
+			));
+		}
+		graph.free_slots = encoded.free_slots;
+
+		for (from, to, data) in encoded.edges {
+			let is_live = |index: usize| matches!(graph.nodes.get(index), Some(Some(_)));
+			if !is_live(from) || !is_live(to) {
+				return Err(D::Error::custom(
+					"edge refers to a node which is not in the graph",
+				));
+			}
+
+			// Make sure that the vectors are large enough to contain the node IDs
+			if graph.edges.len() <= from {
+				graph.edges.resize_with(from + 1, Vec::new);
+			}
+			let row = &mut graph.edges[from];
+			if row.len() <= to {
+				row.resize_with(to + 1, || None);
+			}
+
+			if row[to].replace(data).is_some() {
+				return Err(D::Error::custom("edge appears more than once"));
+			}
+			graph.edge_count += 1;
+		}
+
+		if encoded.reverse_index {
+			graph.enable_reverse_index();
+		}
+		Ok(graph)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use graph_traits::{GraphEdgeAddable, GraphNodeAddable, GraphNodeRemovable};
+
+	use super::*;
+	use crate::{
+		simple_graph::{EdgeID, GraphError},
+		traits::GraphEdgesTo,
+	};
+
+	#[test]
+	fn round_trip_keeps_ids_valid() {
+		let mut graph = SimpleGraph::<String, i32>::with_reverse_index();
+		let nodes = (0..5)
+			.map(|i| graph.add_node(i.to_string()))
+			.collect::<Vec<_>>();
+		for (from, to, weight) in [(0, 2, 1), (2, 4, 2), (4, 0, 3), (2, 2, 4)] {
+			graph.add_edge(nodes[from], nodes[to], weight);
+		}
+		graph.remove_node(nodes[3]);
+		graph.remove_node(nodes[1]);
+
+		let json = serde_json::to_string(&graph).unwrap();
+		let mut copy = serde_json::from_str::<SimpleGraph<String, i32>>(&json).unwrap();
+
+		assert_eq!((copy.node_count(), copy.edge_count()), (3, 4));
+		assert!(copy.has_reverse_index());
+		for node in [nodes[0], nodes[2], nodes[4]] {
+			assert_eq!(copy.try_node(node), graph.try_node(node));
+			assert_eq!(copy.edges_to(node), graph.edges_to(node));
+		}
+		assert_eq!(copy.try_edge(EdgeID(nodes[2], nodes[4])), Ok(&2));
+		assert_eq!(copy.try_node(nodes[1]), Err(GraphError::NodeNotFound));
+
+		// Freed slots are reused in the same order as in the original graph
+		assert_eq!(copy.add_node(String::new()), graph.add_node(String::new()));
+		assert_eq!(copy.add_node(String::new()), graph.add_node(String::new()));
+		assert_eq!(copy.try_node(nodes[1]), Err(GraphError::NodeNotFound));
+		assert_eq!(copy.try_node(nodes[3]), Err(GraphError::NodeNotFound));
+	}
+
+	#[test]
+	fn rejects_inconsistent_input() {
+		let parse = |json: &str| serde_json::from_str::<SimpleGraph<u8, u8>>(json).unwrap_err();
+		let nodes = r#""nodes":[[0,5],[1,6]],"free_slots":[]"#;
+
+		let error = parse(&format!(
+			r#"{{{},"edges":[[0,1,7],[0,1,8]],"reverse_index":false}}"#,
+			nodes
+		));
+		assert!(error.to_string().starts_with("edge appears more than once"));
+		let error = parse(&format!(
+			r#"{{{},"edges":[[0,2,7]],"reverse_index":false}}"#,
+			nodes
+		));
+		assert!(error
+			.to_string()
+			.starts_with("edge refers to a node which is not in the graph"));
+		let error = parse(
+			r#"{"nodes":[[0,5],[1,null]],"edges":[],"free_slots":[1,1],"reverse_index":false}"#,
+		);
+		assert!(error
+			.to_string()
+			.starts_with("free slots do not match the removed nodes"));
+	}
+}
@@ -0,0 +1,30 @@
+use graph_traits::GraphBase;
+
+/// Find all edges leading into a node, the counterpart of `graph_traits::GraphEdgesFrom`
+pub trait GraphEdgesTo: GraphBase {
+	type EdgesToOutput;
+
+	fn edges_to(&self, id: Self::NodeID) -> Self::EdgesToOutput;
+}
+
+/// Find the IDs of every node in the graph, for algorithms which need to visit all of them
+pub trait GraphNodeIDs: GraphBase {
+	type NodeIDsOutput;
+
+	fn node_ids(&self) -> Self::NodeIDsOutput;
+}
+
+/// Node IDs which refer to a numbered slot, so side tables such as `NodeMap` can be stored densely
+///
+/// Equal IDs must give the same index
+pub trait NodeIndex: Copy + Eq {
+	fn node_index(&self) -> usize;
+}
+
+/// Edge IDs which refer to a pair of numbered slots, so side tables such as `EdgeMap` can be
+/// stored densely
+///
+/// Equal IDs must give the same pair of indices
+pub trait EdgeIndex: Copy + Eq {
+	fn edge_index(&self) -> (usize, usize);
+}
@@ -0,0 +1,327 @@
+use graph_traits::{
+	GraphBase, GraphEdgeAddable, GraphEdgeEndpoints, GraphEdgeFrom, GraphEdgeIndexable,
+	GraphEdgeMutIndexable, GraphEdgeRemovable, GraphEdgeTo, GraphEdgesFrom, GraphNodeAddable,
+	GraphNodeIndexable, GraphNodeMutIndexable, GraphNodeRemovable,
+};
+
+use crate::traits::{EdgeIndex, GraphNodeIDs, NodeIndex};
+
+use std::{
+	cmp::{max, min, Ordering},
+	hash::{Hash, Hasher},
+	iter::Iterator,
+};
+
+/// Opaque struct which represents a node in the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeID(usize);
+
+/// Opaque struct which represents an edge in the graph
+///
+/// The ID remembers the direction it was obtained from so that `edge_from` and `edge_to` can be
+/// used to walk the graph, but `A-B` and `B-A` compare, order and hash as the same edge
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeID(usize, usize);
+
+impl EdgeID {
+	/// The endpoints of the edge with the larger node index first, matching the storage layout
+	fn key(self) -> (usize, usize) { (max(self.0, self.1), min(self.0, self.1)) }
+}
+
+impl PartialEq for EdgeID {
+	fn eq(&self, other: &Self) -> bool { self.key() == other.key() }
+}
+
+impl Eq for EdgeID {}
+
+impl PartialOrd for EdgeID {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for EdgeID {
+	fn cmp(&self, other: &Self) -> Ordering { self.key().cmp(&other.key()) }
+}
+
+impl Hash for EdgeID {
+	fn hash<H: Hasher>(&self, state: &mut H) { self.key().hash(state) }
+}
+
+impl NodeIndex for NodeID {
+	fn node_index(&self) -> usize { self.0 }
+}
+
+impl EdgeIndex for EdgeID {
+	fn edge_index(&self) -> (usize, usize) { self.key() }
+}
+
+/// A simple undirected graph implementation.
+///
+/// - Nodes are represented with `Vec<Option<N>>`
+///
+/// - Edges are represented with a lower triangular `Vec<Vec<Option<E>>>`, so the edge between
+///   nodes `A` and `B` is stored once under the larger of the two indices
+///
+/// - All IDs are opaque data structures
+///
+/// Please see the trait implementations for more details
+#[derive(Clone, Debug)]
+pub struct UndirectedGraph<N, E> {
+	nodes: Vec<Option<N>>,
+	edges: Vec<Vec<Option<E>>>,
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+	pub fn new() -> Self {
+		Self {
+			nodes: Vec::new(),
+			edges: Vec::new(),
+		}
+	}
+
+	/// Whether a node is in the graph
+	fn has_node(&self, id: usize) -> bool { matches!(self.nodes.get(id), Some(Some(_))) }
+
+	fn edge_slot(&self, id: EdgeID) -> Option<&Option<E>> {
+		let (hi, lo) = id.key();
+		self.edges.get(hi).and_then(|row| row.get(lo))
+	}
+
+	fn edge_slot_mut(&mut self, id: EdgeID) -> Option<&mut Option<E>> {
+		let (hi, lo) = id.key();
+		self.edges.get_mut(hi).and_then(|row| row.get_mut(lo))
+	}
+}
+
+impl<N, E> Default for UndirectedGraph<N, E> {
+	fn default() -> Self { Self::new() }
+}
+
+impl<N, E> GraphBase for UndirectedGraph<N, E> {
+	type NodeID = NodeID;
+	type EdgeID = EdgeID;
+}
+
+impl<N, E> GraphNodeAddable<N> for UndirectedGraph<N, E> {
+	/// Identical data added twice will return different node IDs
+	fn add_node(&mut self, data: N) -> Self::NodeID {
+		self.nodes.push(Some(data));
+		NodeID(self.nodes.len() - 1)
+	}
+}
+
+impl<N, E> GraphEdgeAddable<E> for UndirectedGraph<N, E>
+where
+	E: Clone,
+{
+	/// Data added between nodes `A` and `B` will overwrite the data that was previously between
+	/// those nodes, whichever order they were given in
+	///
+	/// Panics if either node is not in the graph
+	fn add_edge(
+		&mut self,
+		NodeID(a): Self::NodeID,
+		NodeID(b): Self::NodeID,
+		data: E,
+	) -> Self::EdgeID {
+		assert!(
+			self.has_node(a) && self.has_node(b),
+			"node is not in the graph"
+		);
+		let id = EdgeID(a, b);
+		let (hi, lo) = id.key();
+
+		// Make sure that the vectors are large enough to contain the node IDs
+		if self.edges.len() <= hi {
+			self.edges.resize(hi + 1, Vec::new());
+		}
+		if self.edges[hi].len() <= lo {
+			self.edges[hi].resize(lo + 1, None);
+		}
+
+		self.edges[hi][lo] = Some(data);
+		id
+	}
+}
+
+impl<N, E> GraphNodeRemovable<N> for UndirectedGraph<N, E> {
+	/// Panics if the node is not in the graph
+	///
+	/// Panics if any edges are attached to that node
+	fn remove_node(&mut self, NodeID(id): Self::NodeID) -> N {
+		// Panic if any edges are attached to the removed node
+		if let Some(row) = self.edges.get(id) {
+			assert!(row.iter().all(Option::is_none));
+		}
+		for row in self.edges.iter().skip(id + 1) {
+			assert!(!matches!(row.get(id), Some(Some(_))));
+		}
+
+		self.nodes[id].take().unwrap()
+	}
+}
+
+impl<N, E> GraphEdgeRemovable<E> for UndirectedGraph<N, E> {
+	/// Panics if the edge is not in the graph
+	fn remove_edge(&mut self, id: Self::EdgeID) -> E {
+		self.edge_slot_mut(id).and_then(Option::take).unwrap()
+	}
+}
+
+impl<N, E> GraphNodeIndexable<N> for UndirectedGraph<N, E> {
+	/// Get the data associated with a node
+	///
+	/// Panics if the node is not in the graph
+	fn node(&self, NodeID(id): Self::NodeID) -> &N { self.nodes[id].as_ref().unwrap() }
+}
+
+impl<N, E> GraphNodeMutIndexable<N> for UndirectedGraph<N, E> {
+	/// Get the data associated with a node
+	///
+	/// Panics if the node is not in the graph
+	fn node_mut(&mut self, NodeID(id): Self::NodeID) -> &mut N { self.nodes[id].as_mut().unwrap() }
+}
+
+impl<N, E> GraphEdgeIndexable<E> for UndirectedGraph<N, E> {
+	/// Get the data associated with an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge(&self, id: Self::EdgeID) -> &E { self.edge_slot(id).and_then(Option::as_ref).unwrap() }
+}
+
+impl<N, E> GraphEdgeMutIndexable<E> for UndirectedGraph<N, E> {
+	/// Get the data associated with an edge
+	///
+	/// Panics if the edge is not in the graph
+	fn edge_mut(&mut self, id: Self::EdgeID) -> &mut E {
+		self.edge_slot_mut(id).and_then(Option::as_mut).unwrap()
+	}
+}
+
+impl<N, E> GraphEdgeTo for UndirectedGraph<N, E> {
+	/// Find the node at the far end of an edge, as seen from the node the ID was obtained from
+	fn edge_to(&self, EdgeID(_, id_b): Self::EdgeID) -> Self::NodeID { NodeID(id_b) }
+}
+
+impl<N, E> GraphEdgeFrom for UndirectedGraph<N, E> {
+	/// Find the node at the near end of an edge, as seen from the node the ID was obtained from
+	fn edge_from(&self, EdgeID(id_a, _): Self::EdgeID) -> Self::NodeID { NodeID(id_a) }
+}
+
+impl<N, E> GraphEdgeEndpoints for UndirectedGraph<N, E> {}
+
+impl<N, E> GraphEdgesFrom for UndirectedGraph<N, E> {
+	type EdgesFromOutput = Vec<EdgeID>;
+
+	/// Find all edges attached to a node, whichever side they were added from
+	///
+	/// Each returned ID has `id` as its source, a self loop is returned once
+	///
+	/// Return result in a `Vec`
+	fn edges_from(&self, NodeID(id): Self::NodeID) -> Self::EdgesFromOutput {
+		// Edges to nodes with a smaller or equal index are stored in the row of this node
+		let lower = self.edges.get(id).into_iter().flat_map(|row| {
+			row.iter()
+				.enumerate()
+				.filter_map(move |(i, dest)| dest.as_ref().map(|_| EdgeID(id, i)))
+		});
+
+		// Edges to nodes with a larger index are stored in the rows of those nodes
+		let higher =
+			self.edges
+				.iter()
+				.enumerate()
+				.skip(id + 1)
+				.filter_map(move |(i, row)| match row.get(id) {
+					Some(Some(_)) => Some(EdgeID(id, i)),
+					_ => None,
+				});
+
+		lower.chain(higher).collect()
+	}
+}
+
+impl<N, E> GraphNodeIDs for UndirectedGraph<N, E> {
+	type NodeIDsOutput = Vec<NodeID>;
+
+	/// Find all nodes in the graph
+	///
+	/// Return result in a `Vec`
+	fn node_ids(&self) -> Self::NodeIDsOutput {
+		self.nodes
+			.iter()
+			.enumerate()
+			.filter_map(|(i, node)| node.as_ref().map(|_| NodeID(i)))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::collections::hash_map::DefaultHasher;
+
+	fn hash(id: EdgeID) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		id.hash(&mut hasher);
+		hasher.finish()
+	}
+
+	#[test]
+	fn treats_both_directions_as_one_edge() {
+		let mut graph = UndirectedGraph::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let forward = graph.add_edge(a, b, 1);
+		let backward = graph.add_edge(b, a, 2);
+
+		assert_eq!(forward, backward);
+		assert_eq!(hash(forward), hash(backward));
+		assert_eq!(graph.edge_from(backward), b);
+		assert_eq!(*graph.edge(forward), 2);
+		assert_eq!(graph.edges_from(a), vec![forward]);
+		assert_eq!(graph.edges_from(b), vec![backward]);
+		assert_eq!(graph.remove_edge(backward), 2);
+		assert!(graph.edges_from(a).is_empty());
+	}
+
+	#[test]
+	fn returns_self_loops_once() {
+		let mut graph = UndirectedGraph::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		let edge = graph.add_edge(a, a, ());
+		graph.add_edge(b, a, ());
+		assert_eq!(graph.edges_from(a), vec![edge, EdgeID(0, 1)]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn refuses_to_remove_nodes_with_edges_to_lower_nodes() {
+		let mut graph = UndirectedGraph::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		graph.add_edge(a, b, ());
+		graph.remove_node(b);
+	}
+
+	#[test]
+	#[should_panic]
+	fn refuses_to_remove_nodes_with_edges_to_higher_nodes() {
+		let mut graph = UndirectedGraph::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		graph.add_edge(b, a, ());
+		graph.remove_node(a);
+	}
+
+	#[test]
+	#[should_panic(expected = "node is not in the graph")]
+	fn rejects_edges_to_removed_nodes() {
+		let mut graph = UndirectedGraph::<(), ()>::new();
+		let a = graph.add_node(());
+		let b = graph.add_node(());
+		graph.remove_node(b);
+		graph.add_edge(a, b, ());
+	}
+}
@@ -0,0 +1,170 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A disjoint-set forest tracking which keys, such as node IDs, are connected to each other
+///
+/// Keys are added the first time they are passed to `insert` or `union`, starting in a set of
+/// their own
+///
+/// Uses union by rank and path halving, so every operation takes close to constant time
+#[derive(Clone, Debug)]
+pub struct UnionFind<K> {
+	indices: HashMap<K, usize>,
+	keys: Vec<K>,
+	parents: Vec<usize>,
+	ranks: Vec<u8>,
+	set_count: usize,
+}
+
+impl<K> UnionFind<K>
+where
+	K: Copy + Eq + Hash,
+{
+	pub fn new() -> Self {
+		Self {
+			indices: HashMap::new(),
+			keys: Vec::new(),
+			parents: Vec::new(),
+			ranks: Vec::new(),
+			set_count: 0,
+		}
+	}
+
+	/// The number of keys which have been added
+	pub fn len(&self) -> usize { self.keys.len() }
+
+	/// Whether no keys have been added
+	pub fn is_empty(&self) -> bool { self.keys.is_empty() }
+
+	/// The number of disjoint sets
+	pub fn set_count(&self) -> usize { self.set_count }
+
+	/// Whether a key has been added
+	pub fn contains(&self, key: K) -> bool { self.indices.contains_key(&key) }
+
+	/// Add a key in a set of its own, returning `false` if it was already present
+	pub fn insert(&mut self, key: K) -> bool {
+		if self.contains(key) {
+			return false;
+		}
+		self.index(key);
+		true
+	}
+
+	/// Find the index of a key, adding it if needed
+	fn index(&mut self, key: K) -> usize {
+		if let Some(index) = self.indices.get(&key) {
+			return *index;
+		}
+		let index = self.keys.len();
+		self.indices.insert(key, index);
+		self.keys.push(key);
+		self.parents.push(index);
+		self.ranks.push(0);
+		self.set_count += 1;
+		index
+	}
+
+	fn find_index(&mut self, mut index: usize) -> usize {
+		while self.parents[index] != index {
+			let grandparent = self.parents[self.parents[index]];
+			self.parents[index] = grandparent;
+			index = grandparent;
+		}
+		index
+	}
+
+	/// Find the representative key of the set containing `key`, or `None` if it was never added
+	///
+	/// Two keys are in the same set exactly when they have the same representative
+	pub fn find(&mut self, key: K) -> Option<K> {
+		let index = *self.indices.get(&key)?;
+		let root = self.find_index(index);
+		Some(self.keys[root])
+	}
+
+	/// Merge the sets containing `a` and `b`, adding either key if needed
+	///
+	/// Returns `false` if they were already in the same set
+	pub fn union(&mut self, a: K, b: K) -> bool {
+		let (a, b) = (self.index(a), self.index(b));
+		let (a, b) = (self.find_index(a), self.find_index(b));
+		if a == b {
+			return false;
+		}
+
+		// Hang the shallower tree beneath the deeper one
+		let (parent, child) = if self.ranks[a] < self.ranks[b] {
+			(b, a)
+		}
+		else {
+			(a, b)
+		};
+		self.parents[child] = parent;
+		if self.ranks[parent] == self.ranks[child] {
+			self.ranks[parent] += 1;
+		}
+		self.set_count -= 1;
+		true
+	}
+
+	/// Whether `a` and `b` are in the same set, which is never the case for keys not yet added
+	pub fn equiv(&mut self, a: K, b: K) -> bool {
+		match (self.find(a), self.find(b)) {
+			(Some(a), Some(b)) => a == b,
+			_ => false,
+		}
+	}
+
+	/// Group every key by the set it belongs to, in the order the keys were added
+	pub fn sets(&mut self) -> Vec<Vec<K>> {
+		let mut positions = HashMap::new();
+		let mut sets = Vec::<Vec<K>>::new();
+		for index in 0..self.keys.len() {
+			let root = self.find_index(index);
+			let position = *positions.entry(root).or_insert_with(|| {
+				sets.push(Vec::new());
+				sets.len() - 1
+			});
+			sets[position].push(self.keys[index]);
+		}
+		sets
+	}
+}
+
+impl<K> Default for UnionFind<K>
+where
+	K: Copy + Eq + Hash,
+{
+	fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn merges_sets() {
+		let mut sets = UnionFind::new();
+		for key in 0..6 {
+			assert!(sets.insert(key));
+		}
+		assert!(!sets.insert(0));
+		assert_eq!(sets.set_count(), 6);
+
+		assert!(sets.union(0, 1));
+		assert!(sets.union(2, 3));
+		assert!(sets.union(1, 3));
+		assert!(!sets.union(0, 2));
+		assert_eq!(sets.set_count(), 3);
+		assert!(sets.equiv(0, 3));
+		assert!(!sets.equiv(0, 4));
+		assert_eq!(sets.find(2), sets.find(1));
+		assert_eq!(sets.find(6), None);
+		assert!(!sets.equiv(6, 6));
+		assert_eq!(sets.sets(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+
+		assert!(sets.union(6, 5));
+		assert_eq!(sets.len(), 7);
+		assert_eq!(sets.set_count(), 3);
+	}
+}